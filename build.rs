@@ -0,0 +1,48 @@
+/*
+ * FileName:        build
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 23:40:12
+ * LastModified:    2026-10-19 23:40:12
+ * Reference:       8ucchiman.github.io
+ * Description:     ライブラリのソース（テンプレート・CSS・JS を含む）のハッシュを TEMPLATE_HASH に入れる
+ *
+ * .build-cache の入力ハッシュに使う。実行ファイルのパスや更新時刻ではなく中身で決まるので、
+ * 何も変えずにビルドし直しても使い回せ、ソースが変われば必ず描画し直す。
+ */
+
+
+use std::{fs, path::Path};
+
+#[path = "src/sha1.rs"]
+mod sha1;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+    let mut files = Vec::new();
+    collect(Path::new("src"), &mut files);
+    files.sort();
+    let mut data = Vec::new();
+    for file in &files {
+        // main.rs はバイナリ側の設定（Site）だけで、その中身はページごとの入力ハッシュに入る
+        if file == Path::new("src/main.rs") {
+            continue;
+        }
+        let bytes = fs::read(file).unwrap_or_else(|e| panic!("{}: {e}", file.display()));
+        for part in [file.to_string_lossy().as_bytes(), &bytes] {
+            data.extend_from_slice(&(part.len() as u64).to_le_bytes());
+            data.extend_from_slice(part);
+        }
+    }
+    println!("cargo:rustc-env=TEMPLATE_HASH={}", sha1::hex(&sha1::digest(&data)));
+}
+
+fn collect(dir: &Path, out: &mut Vec<std::path::PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap_or_else(|e| panic!("{}: {e}", dir.display())) {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect(&path, out);
+        } else if path.extension().is_some_and(|x| x == "rs") {
+            out.push(path);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ * FileName:        about
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 11:31:48
+ * LastModified:    2026-10-19 11:31:48
+ * Reference:       8ucchiman.github.io
+ * Description:     About セクション（Markdown の自己紹介・肩書き・ポートレート・ボタン）
+ */
+
+
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::{
+    contacts::{self, Contact},
+    error::{BuildError, IoResultExt},
+    html_attr, html_escape, markdown,
+    sections::Section,
+    Files,
+};
+
+#[derive(Clone, Default)]
+pub struct About {
+    /// 見出し上の小さな文字（"Rust / Robotics / CV" など）
+    pub kicker: String,
+    pub title: String,
+    pub name: String,
+    /// " / " でつないで表示する
+    pub roles: Vec<String>,
+    /// Markdown
+    pub bio: String,
+    /// リポジトリ直下からのパス。ビルド時に dist の同じ場所へコピーする
+    pub portrait: String,
+    pub portrait_alt: String,
+    pub actions: Vec<Action>,
+}
+
+/// ボタン 1 つ。`contact` は contacts の `kind`。
+#[derive(Clone, Default)]
+pub struct Action {
+    pub contact: String,
+    /// 空なら連絡先の label
+    pub label: String,
+}
+
+/// `root` のポートレートを出力に加え、使えたかを返す。ファイルが無ければ警告してフォールバック表示にする。
+pub fn copy_portrait(about: &About, root: &Path, files: &mut Files) -> Result<bool, BuildError> {
+    let src = root.join(&about.portrait);
+    if about.portrait.is_empty() {
+        return Ok(false);
+    }
+    if !src.is_file() {
+        eprintln!("warning: about: portrait `{}` not found, using a placeholder", about.portrait);
+        return Ok(false);
+    }
+    files.insert(PathBuf::from(&about.portrait), fs::read(&src).at(&src)?);
+    Ok(true)
+}
+
+/// ボタンが参照する連絡先が全部あるか
+pub fn validate(about: &About, contacts: &[Contact]) -> Result<(), BuildError> {
+    for a in &about.actions {
+        if contacts::find(contacts, &a.contact).is_none() {
+            return Err(BuildError::config(format!("about: action refers to unknown contact `{}`", a.contact)));
+        }
+    }
+    Ok(())
+}
+
+pub fn section_html(s: &Section, about: &About, contacts: &[Contact], has_portrait: bool) -> String {
+    let media = if has_portrait {
+        format!(r#"<img src="{}" alt="{}">"#, html_attr(&about.portrait), html_attr(&about.portrait_alt))
+    } else {
+        // 画像が無いときは名前の頭文字を出す
+        let initial: String = about.name.chars().take(1).collect();
+        format!(
+            r#"<div class="about-fallback" role="img" aria-label="{}">{}</div>"#,
+            html_attr(&about.portrait_alt),
+            html_escape(&initial.to_uppercase()),
+        )
+    };
+    let actions: String = about
+        .actions
+        .iter()
+        .enumerate()
+        .filter_map(|(i, a)| {
+            let c = contacts::find(contacts, &a.contact)?;
+            let label = if a.label.is_empty() { &c.label } else { &a.label };
+            // 連絡先の一覧で隠しているアドレスはボタンでも同じように隠す
+            Some(format!(
+                "\n            <a class=\"btn{}\"{} target=\"_blank\" rel=\"noreferrer\">{}</a>",
+                if i == 0 { "" } else { " ghost" },
+                contacts::href_attrs(c),
+                contacts::text_html(c, label),
+            ))
+        })
+        .collect();
+    let kicker = if about.kicker.is_empty() { String::new() } else {
+        format!("\n        <p class=\"about-kicker\">{}</p>", html_escape(&about.kicker))
+    };
+    let role = if about.roles.is_empty() { String::new() } else {
+        format!("\n          <p class=\"about-role\">{}</p>", html_escape(&about.roles.join(" / ")))
+    };
+
+    format!(
+        r#"<section class="section" id="{id}" aria-label="{label}">
+  <div class="container">
+    <div class="about-grid">
+      <div class="about-media">
+        <div class="about-frame">
+          {media}
+        </div>
+      </div>
+      <div class="about-text">{kicker}
+        <h3 class="about-title">{title}</h3>
+        <div class="about-card">
+          <h4 class="about-name">{name}</h4>{role}
+          <div class="about-bio">{bio}</div>
+          <div class="about-actions">{actions}
+          </div>
+        </div>
+      </div>
+    </div>
+  </div>
+</section>"#,
+        id = html_attr(&s.id),
+        label = html_attr(&s.label),
+        title = html_escape(&about.title),
+        name = html_escape(&about.name),
+        bio = markdown::to_html(&about.bio).trim_end(),
+    )
+}
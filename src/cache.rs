@@ -0,0 +1,294 @@
+/*
+ * FileName:        cache
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 21:58:30
+ * LastModified:    2026-10-19 21:58:30
+ * Reference:       8ucchiman.github.io
+ * Description:     インクリメンタルビルドのマニフェスト（<root>/.build-cache）
+ *
+ * 1 行目は `out<TAB><出力先>`（root からの相対パス、root の外なら絶対パス。どちらも正規化したもの）、以降は `<入力ハッシュ><TAB><出力ハッシュ><TAB><dist からのパス>`。
+ * 入力ハッシュはテンプレート（ライブラリのソースのハッシュ）・バージョン・そのページが使う設定と本文から作る。
+ * 入力が同じで、出力先のファイルが記録どおりなら描画せずにそれを使い回す。
+ */
+
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use crate::{
+    error::{BuildError, IoResultExt},
+    sha1, Files,
+};
+
+/// マニフェストのファイル名（root 直下）
+pub const FILE: &str = ".build-cache";
+
+/// 前回のビルドの記録
+#[derive(Default)]
+pub struct BuildCache {
+    /// 出力パス → (入力ハッシュ, 出力ハッシュ)
+    entries: BTreeMap<PathBuf, (String, String)>,
+}
+
+impl BuildCache {
+    /// `path` を読む。無い・壊れている・出力先が違うなら空（全部描画し直す）
+    pub fn load(path: &Path, out: &Path) -> BuildCache {
+        let Ok(text) = fs::read_to_string(path) else { return BuildCache::default() };
+        let mut lines = text.lines();
+        if lines.next() != Some(&format!("out\t{}", out_key(path, out))) {
+            return BuildCache::default();
+        }
+        let entries = lines
+            .filter_map(|l| {
+                let mut it = l.splitn(3, '\t');
+                let (input, output, file) = (it.next()?, it.next()?, it.next()?);
+                Some((PathBuf::from(file), (input.to_string(), output.to_string())))
+            })
+            .collect();
+        BuildCache { entries }
+    }
+
+    /// 今回の出力を記録する。`inputs` に無いもの（毎回描画するもの）は出力ハッシュを入力ハッシュとして残す
+    pub fn save(path: &Path, out: &Path, files: &Files, inputs: &BTreeMap<PathBuf, String>) -> Result<(), BuildError> {
+        let mut text = format!("out\t{}\n", out_key(path, out));
+        for (file, bytes) in files {
+            let output = hash(bytes);
+            let input = inputs.get(file).unwrap_or(&output);
+            text.push_str(&format!("{input}\t{output}\t{}\n", file.display()));
+        }
+        // 中身が同じなら書かない（mtime を動かさない）
+        if fs::read_to_string(path).is_ok_and(|old| old == text) {
+            return Ok(());
+        }
+        fs::write(path, text).at(path)
+    }
+}
+
+/// SiteBuilder::write_to() の結果
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    /// 今回描画したもの
+    pub rebuilt: Vec<PathBuf>,
+    /// 入力が前回と同じで、出力先のファイルをそのまま使ったもの
+    pub reused: Vec<PathBuf>,
+    /// 中身が変わって書き出したもの（同じなら触らない）
+    pub written: Vec<PathBuf>,
+    /// 出力先にあって今回は出していない、`keep` にも当たらないファイル（消す）
+    pub stale: Vec<PathBuf>,
+    /// true なら何も書かず・消さず、`written` と `stale` はそうするはずのものの一覧
+    pub dry_run: bool,
+}
+
+impl fmt::Display for BuildReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (write, remove) = if self.dry_run { ("would write", "would remove") } else { ("wrote", "removed") };
+        writeln!(
+            f,
+            "rebuilt {} of {} output(s), reused {} from {FILE}; {write} {} changed file(s)",
+            self.rebuilt.len(),
+            self.rebuilt.len() + self.reused.len(),
+            self.reused.len(),
+            self.written.len(),
+        )?;
+        for path in &self.written {
+            writeln!(f, "  {write} {}", path.display())?;
+        }
+        if !self.stale.is_empty() {
+            writeln!(f, "{remove} {} stale file(s)", self.stale.len())?;
+        }
+        for path in &self.stale {
+            writeln!(f, "  {remove} {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// 描画中に使う前回の記録と、今回の入力ハッシュ・使い回したもの
+pub struct Incremental<'c> {
+    prev: Option<(&'c BuildCache, &'c Path)>,
+    inputs: Mutex<BTreeMap<PathBuf, String>>,
+    reused: Mutex<BTreeSet<PathBuf>>,
+}
+
+impl<'c> Incremental<'c> {
+    /// 何も使い回さない（入力ハッシュは記録する）
+    pub fn none() -> Incremental<'static> {
+        Incremental { prev: None, inputs: Mutex::default(), reused: Mutex::default() }
+    }
+
+    /// 前回 `out` に書いた結果を使い回す
+    pub fn from(cache: &'c BuildCache, out: &'c Path) -> Incremental<'c> {
+        Incremental { prev: Some((cache, out)), inputs: Mutex::default(), reused: Mutex::default() }
+    }
+
+    /// 入力ハッシュが前回と同じで、出力先のファイルも記録どおりならその中身を、そうでなければ `render` の結果を返す
+    pub fn get_or_render(&self, path: &Path, input: String, render: impl FnOnce() -> Vec<u8>) -> Vec<u8> {
+        let reused = self.prev.and_then(|(cache, out)| {
+            let (prev_input, prev_output) = cache.entries.get(path)?;
+            if *prev_input != input {
+                return None;
+            }
+            let bytes = fs::read(out.join(path)).ok()?;
+            (hash(&bytes) == *prev_output).then_some(bytes)
+        });
+        self.inputs.lock().unwrap().insert(path.to_path_buf(), input);
+        match reused {
+            Some(bytes) => {
+                self.reused.lock().unwrap().insert(path.to_path_buf());
+                bytes
+            }
+            None => render(),
+        }
+    }
+
+    /// (入力ハッシュ, 使い回したパス)
+    pub fn finish(self) -> (BTreeMap<PathBuf, String>, BTreeSet<PathBuf>) {
+        (self.inputs.into_inner().unwrap(), self.reused.into_inner().unwrap())
+    }
+}
+
+/// 入力ハッシュを作る。区切りが曖昧にならないよう、各部分は長さ付きで足す
+pub struct Fingerprint(Vec<u8>);
+
+impl Fingerprint {
+    /// テンプレート（ライブラリのソースのハッシュ）から始める
+    pub fn new() -> Fingerprint {
+        let mut fp = Fingerprint(Vec::new());
+        fp.add(template_id());
+        fp
+    }
+
+    pub fn add(&mut self, part: impl AsRef<[u8]>) -> &mut Self {
+        let part = part.as_ref();
+        self.0.extend_from_slice(&(part.len() as u64).to_le_bytes());
+        self.0.extend_from_slice(part);
+        self
+    }
+
+    /// 列を足す（件数も足すので、隣の部分と混ざらない）
+    pub fn add_list<S: AsRef<[u8]>>(&mut self, parts: &[S]) -> &mut Self {
+        self.add((parts.len() as u64).to_le_bytes());
+        for part in parts {
+            self.add(part);
+        }
+        self
+    }
+
+    pub fn finish(&self) -> String {
+        hash(&self.0)
+    }
+}
+
+/// テンプレートの識別子。ライブラリのソース（HTML・CSS・JS のテンプレートを含む）のハッシュで、build.rs が作る
+fn template_id() -> &'static str {
+    concat!(env!("CARGO_PKG_VERSION"), " ", env!("TEMPLATE_HASH"))
+}
+
+/// マニフェストに書く出力先。`--out dist`・`--out ./dist`・絶対パスが同じ出力先になるよう正規化し、
+/// マニフェストのあるディレクトリ（root）の中なら相対パスにする（チェックアウトを動かしても使い回せる）
+fn out_key(manifest: &Path, out: &Path) -> String {
+    let resolve = |p: &Path| p.canonicalize().or_else(|_| std::path::absolute(p)).unwrap_or_else(|_| p.to_path_buf());
+    let out = resolve(out);
+    let root = resolve(manifest.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(Path::new(".")));
+    out.strip_prefix(&root).unwrap_or(&out).display().to_string()
+}
+
+fn hash(bytes: &[u8]) -> String {
+    sha1::hex(&sha1::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 前回のビルドとして `out/page.html` を書き、入力 "v1" で記録した root と out
+    fn built(name: &str) -> (PathBuf, PathBuf) {
+        let root = std::env::temp_dir().join(format!("bucchiman-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let out = root.join("dist");
+        fs::create_dir_all(&out).unwrap();
+        fs::write(out.join("page.html"), "old").unwrap();
+        let files: Files = [(PathBuf::from("page.html"), b"old".to_vec())].into();
+        let inputs = [(PathBuf::from("page.html"), "v1".to_string())].into();
+        BuildCache::save(&root.join(FILE), &out, &files, &inputs).unwrap();
+        (root, out)
+    }
+
+    /// 入力 `input` で page.html を求め、(中身, 使い回したか)
+    fn get(cache: &BuildCache, out: &Path, input: &str) -> (String, bool) {
+        let inc = Incremental::from(cache, out);
+        let bytes = inc.get_or_render(Path::new("page.html"), input.to_string(), || b"new".to_vec());
+        let (inputs, reused) = inc.finish();
+        assert_eq!(inputs[Path::new("page.html")], input);
+        (String::from_utf8(bytes).unwrap(), reused.contains(Path::new("page.html")))
+    }
+
+    #[test]
+    fn unchanged_input_reuses_the_output() {
+        let (root, out) = built("reuse");
+        let cache = BuildCache::load(&root.join(FILE), &out);
+        assert_eq!(get(&cache, &out, "v1"), ("old".to_string(), true));
+    }
+
+    #[test]
+    fn changed_input_rebuilds() {
+        let (root, out) = built("input");
+        let cache = BuildCache::load(&root.join(FILE), &out);
+        assert_eq!(get(&cache, &out, "v2"), ("new".to_string(), false));
+    }
+
+    #[test]
+    fn edited_or_missing_output_rebuilds() {
+        let (root, out) = built("edited");
+        let cache = BuildCache::load(&root.join(FILE), &out);
+        fs::write(out.join("page.html"), "hand edit").unwrap();
+        assert_eq!(get(&cache, &out, "v1"), ("new".to_string(), false));
+        fs::remove_file(out.join("page.html")).unwrap();
+        assert_eq!(get(&cache, &out, "v1"), ("new".to_string(), false));
+    }
+
+    #[test]
+    fn another_out_dir_ignores_the_manifest() {
+        let (root, out) = built("out");
+        let other = root.join("public");
+        fs::create_dir_all(&other).unwrap();
+        fs::copy(out.join("page.html"), other.join("page.html")).unwrap();
+        let cache = BuildCache::load(&root.join(FILE), &other);
+        assert!(cache.entries.is_empty());
+        assert_eq!(get(&cache, &other, "v1"), ("new".to_string(), false));
+        assert!(BuildCache::load(&root.join(FILE), &out).entries.contains_key(Path::new("page.html")));
+    }
+
+    #[test]
+    fn spellings_of_the_same_out_dir_share_the_manifest() {
+        let (root, out) = built("spelling");
+        assert_eq!(fs::read_to_string(root.join(FILE)).unwrap().lines().next(), Some("out\tdist"));
+        fs::create_dir_all(root.join("public")).unwrap();
+        for same in [root.join("./dist"), root.join("dist/"), root.join("public/../dist"), out.canonicalize().unwrap()] {
+            let cache = BuildCache::load(&root.join(FILE), &same);
+            assert_eq!(get(&cache, &same, "v1"), ("old".to_string(), true), "{}", same.display());
+        }
+    }
+
+    #[test]
+    fn save_keeps_an_identical_manifest_untouched() {
+        let (root, out) = built("save");
+        let manifest = root.join(FILE);
+        let before = fs::metadata(&manifest).unwrap().modified().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let files: Files = [(PathBuf::from("page.html"), b"old".to_vec())].into();
+        BuildCache::save(&manifest, &out, &files, &[(PathBuf::from("page.html"), "v1".to_string())].into()).unwrap();
+        assert_eq!(fs::metadata(&manifest).unwrap().modified().unwrap(), before);
+    }
+
+    #[test]
+    fn fingerprint_parts_do_not_run_together() {
+        assert_ne!(Fingerprint::new().add("ab").add("c").finish(), Fingerprint::new().add("a").add("bc").finish());
+        assert_eq!(Fingerprint::new().add("a").finish(), Fingerprint::new().add("a").finish());
+        assert_ne!(Fingerprint::new().add_list(&["a", "b"]).add_list(&["c"]).finish(), Fingerprint::new().add_list(&["a"]).add_list(&["b", "c"]).finish());
+    }
+}
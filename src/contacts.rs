@@ -0,0 +1,155 @@
+/*
+ * FileName:        contacts
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 11:20:05
+ * LastModified:    2026-10-19 11:20:05
+ * Reference:       8ucchiman.github.io
+ * Description:     連絡先・SNS のリンク一覧
+ */
+
+
+use crate::{error::BuildError, html_attr, html_escape, icons::Icons, sections::Section};
+
+/// 連絡先 1 件。`kind` は About のボタンなどから参照するキー（"github", "blog" など）。
+/// kind が "email" のものはアイコンではなくアドレスの行として出す。
+#[derive(Clone, Default)]
+pub struct Contact {
+    pub kind: String,
+    pub url: String,
+    pub label: String,
+    /// HTML 上での URL の隠し方（スクレイパー対策）
+    pub obfuscate: Obfuscate,
+}
+
+/// 連絡先 URL の難読化
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum Obfuscate {
+    /// そのまま
+    #[default]
+    Plain,
+    /// 文字参照（&#109;...）にする。ブラウザがそのまま解釈するので JS は要らない
+    Entities,
+    /// 逆順にして data 属性に入れ、JS で戻す。JS が無ければ "user [at] example [dot] com" の表記だけ出す
+    Reversed,
+    /// ROT13 にして data 属性に入れ、JS で戻す（JS 無しは Reversed と同じ）
+    Rot13,
+}
+
+/// `href` 相当の属性。data 属性に入れるものは app.js が復元して href を付ける（About のボタンも同じものを使う）
+pub(crate) fn href_attrs(c: &Contact) -> String {
+    match c.obfuscate {
+        Obfuscate::Plain => format!(r#" href="{}""#, html_attr(&c.url)),
+        Obfuscate::Entities => format!(r#" href="{}""#, entities(&c.url)),
+        Obfuscate::Reversed => format!(r#" data-contact="{}" data-contact-enc="rev""#, html_attr(&c.url.chars().rev().collect::<String>())),
+        Obfuscate::Rot13 => format!(r#" data-contact="{}" data-contact-enc="rot13""#, html_attr(&rot13(&c.url))),
+    }
+}
+
+/// 表示用の文字列。JS で復元するものは no-JS 向けに読める形だけ出す
+pub(crate) fn text_html(c: &Contact, text: &str) -> String {
+    match c.obfuscate {
+        Obfuscate::Plain => html_escape(text),
+        Obfuscate::Entities => entities(text),
+        Obfuscate::Reversed | Obfuscate::Rot13 => html_escape(&text.replace('@', " [at] ").replace('.', " [dot] ")),
+    }
+}
+
+/// すべての文字を数値文字参照にする（10 進と 16 進を交互に混ぜる）
+fn entities(s: &str) -> String {
+    s.chars()
+        .enumerate()
+        .map(|(i, c)| if i % 2 == 0 { format!("&#{};", c as u32) } else { format!("&#x{:x};", c as u32) })
+        .collect()
+}
+
+fn rot13(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'a'..='z' => (((c as u8 - b'a') + 13) % 26 + b'a') as char,
+            'A'..='Z' => (((c as u8 - b'A') + 13) % 26 + b'A') as char,
+            _ => c,
+        })
+        .collect()
+}
+
+pub fn find<'c>(contacts: &'c [Contact], kind: &str) -> Option<&'c Contact> {
+    contacts.iter().find(|c| c.kind == kind)
+}
+
+/// URL がスキーム付き（https:, mailto:, tel: など）で、空白を含まないか
+pub fn validate(contacts: &[Contact]) -> Result<(), BuildError> {
+    for c in contacts {
+        let scheme = c.url.split_once(':').map_or("", |(s, _)| s);
+        let message = if scheme.is_empty() || !scheme.starts_with(|ch: char| ch.is_ascii_alphabetic())
+            || !scheme.chars().all(|ch| ch.is_ascii_alphanumeric() || "+-.".contains(ch))
+        {
+            "needs a scheme such as `https:` or `mailto:`"
+        } else if c.url.contains(char::is_whitespace) {
+            "must not contain whitespace"
+        } else {
+            continue;
+        };
+        return Err(BuildError::InvalidUrl { url: c.url.clone(), field: format!("contact `{}`", c.kind), message: message.into() });
+    }
+    Ok(())
+}
+
+/// アイコンが無い kind に使うもの
+const FALLBACK_ICON: &str = "link";
+
+/// `qr` が true なら vCard の QR コード（assets/qr-contact.svg）と contact.vcf へのリンクも出す。
+/// `year` はクレジットの年（ビルド時刻から。再現可能なビルドのため現在時刻は見ない）
+pub fn section_html(s: &Section, contacts: &[Contact], icons: &Icons, ver: &str, qr: bool, year: i64) -> String {
+    let (emails, others): (Vec<&Contact>, Vec<&Contact>) = contacts.iter().partition(|c| c.kind == "email");
+    let links: String = others
+        .iter()
+        .map(|c| {
+            let icon = icons.html(&c.kind, ver).or_else(|| {
+                eprintln!("warning: contact `{}`: no icon named `{}`, using `{FALLBACK_ICON}`", c.label, c.kind);
+                icons.html(FALLBACK_ICON, ver)
+            });
+            format!(
+                "\n      <a class=\"icon-link\"{} target=\"_blank\" rel=\"noreferrer\" aria-label=\"{label}\" title=\"{label}\">\n        {}\n      </a>",
+                href_attrs(c),
+                icon.unwrap_or_else(|| html_escape(&c.label)),
+                label = html_attr(&c.label),
+            )
+        })
+        .collect();
+    let mails: String = emails
+        .iter()
+        .map(|c| {
+            let addr = c.url.strip_prefix("mailto:").unwrap_or(&c.url);
+            // data 属性で隠すものは JS が復元したアドレスで本文も置き換える
+            let replace = if matches!(c.obfuscate, Obfuscate::Reversed | Obfuscate::Rot13) { " data-contact-text" } else { "" };
+            format!(
+                "\n      <p class=\"contact-line\"><a class=\"mail-link\"{}{replace}>{}</a></p>",
+                href_attrs(c),
+                text_html(c, addr),
+            )
+        })
+        .collect();
+    let qr = if qr {
+        format!(
+            "\n      <figure class=\"contact-qr\"><img src=\"assets/qr-contact.svg?v={v}\" alt=\"QR code for the contact card\"><figcaption><a class=\"mail-link\" href=\"contact.vcf?v={v}\" download=\"contact.vcf\">contact.vcf</a></figcaption></figure>",
+            v = html_attr(ver),
+        )
+    } else {
+        String::new()
+    };
+    format!(r##"<section class="section" id="{id}" aria-label="{label}">
+  <div class="container">
+    
+    <div class="contact-column">
+    <div class="contact-links">{links}
+    </div>
+    <div class="contact-meta">{mails}{qr}
+      <p class="contact-credit">YUKI IWABUCHI &copy; {year}</p>
+    </div>
+    </div>
+  </div>
+</section>"##,
+        id = html_attr(&s.id),
+        label = html_attr(&s.label),
+    )
+}
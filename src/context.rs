@@ -0,0 +1,157 @@
+/*
+ * FileName:        context
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 18:10:37
+ * LastModified:    2026-10-19 18:10:37
+ * Reference:       8ucchiman.github.io
+ * Description:     ビルドの入力（ソースのルート・環境変数・時刻・バージョン）
+ */
+
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{error::BuildError, git::GitInfo};
+
+/// ビルドが外から受け取るものを全部まとめたもの。
+/// SiteBuilder はカレントディレクトリやプロセスの環境変数を直接見ず、これだけを使う。
+#[derive(Clone, Debug)]
+pub struct BuildContext {
+    /// 入力（taxonomy.toml, content/, assets/, cache/ など）を読むディレクトリ
+    pub root: PathBuf,
+    /// ビルド時刻（UNIX 秒）。年号など時刻に依存する値は全部ここから作る
+    pub time: u64,
+    /// `time` を現在時刻から取った（SOURCE_DATE_EPOCH もコミットも無い）。このビルドは再現できない
+    pub clock: bool,
+    /// キャッシュバスター（`?v=`）
+    pub version: String,
+    /// root のリポジトリの今のコミット（リポジトリでなければ None）
+    pub git: Option<GitInfo>,
+}
+
+impl Default for BuildContext {
+    /// カレントディレクトリ・時刻 0・バージョン "dev"（テスト向けの固定値）
+    fn default() -> Self {
+        BuildContext { root: PathBuf::from("."), time: 0, clock: false, version: "dev".to_string(), git: None }
+    }
+}
+
+impl BuildContext {
+    /// プロセスの環境変数と root のリポジトリから作る（from_env_map() を参照）
+    pub fn from_env(root: &Path) -> Result<BuildContext, BuildError> {
+        BuildContext::from_env_map(root, &std::env::vars().collect())
+    }
+
+    /// 環境変数 `env` と root のリポジトリから作る（プロセスの環境は見ない）。バージョンは `ASSET_VER`（本番は Actions から github.sha を渡す）→
+    /// 今のコミットの短いハッシュ（変更があれば `-dirty` 付き）→ "dev" の順。
+    ///
+    /// 時刻は同じコミットなら同じ出力になるように、`SOURCE_DATE_EPOCH` → `root` の最後のコミットの時刻 →
+    /// 現在時刻（再現できない旨を警告）の順で決める。`SOURCE_DATE_EPOCH` が数値でなければエラー。
+    pub fn from_env_map(root: &Path, env: &BTreeMap<String, String>) -> Result<BuildContext, BuildError> {
+        let git = GitInfo::read(root);
+        let (time, clock) = match env.get("SOURCE_DATE_EPOCH") {
+            Some(v) => {
+                let time = v.trim().parse().map_err(|_| BuildError::config(format!("SOURCE_DATE_EPOCH `{v}` is not a UNIX timestamp")))?;
+                (time, false)
+            }
+            None => match git.as_ref().and_then(|g| g.time) {
+                Some(time) => (time, false),
+                None => {
+                    eprintln!("warning: neither SOURCE_DATE_EPOCH nor a git commit is available, using the current time (the build is not reproducible)");
+                    (SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()), true)
+                }
+            },
+        };
+        let version = match (env.get("ASSET_VER"), &git) {
+            (Some(v), _) => v.clone(),
+            (None, Some(g)) => format!("{}{}", g.short(), if g.dirty { "-dirty" } else { "" }),
+            (None, None) => "dev".to_string(),
+        };
+        Ok(BuildContext { root: root.to_path_buf(), time, clock, version, git })
+    }
+
+    /// `time` の年（UTC）。フッターの著作権表示に使う
+    pub fn year(&self) -> i64 {
+        civil_from_days((self.time / 86_400) as i64).0
+    }
+
+    /// `time` の ISO 8601 表記（UTC、`2026-10-19T10:40:18Z`）
+    pub fn timestamp(&self) -> String {
+        let (y, m, d) = civil_from_days((self.time / 86_400) as i64);
+        let secs = self.time % 86_400;
+        format!("{y:04}-{m:02}-{d:02}T{:02}:{:02}:{:02}Z", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+
+    /// ルート基準のパス
+    pub fn path(&self, rel: impl AsRef<Path>) -> PathBuf {
+        self.root.join(rel)
+    }
+}
+
+/// 1970-01-01 からの日数 → (年, 月, 日)（proleptic Gregorian）
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    (y, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> BTreeMap<String, String> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    /// リポジトリの外の空のディレクトリ
+    fn no_repo(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bucchiman-context-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn from_env_map_reads_only_the_given_variables() {
+        let root = no_repo("vars");
+        let ctx = BuildContext::from_env_map(&root, &env(&[("SOURCE_DATE_EPOCH", " 1735689600\n"), ("ASSET_VER", "abc123")])).unwrap();
+        assert_eq!((ctx.time, ctx.clock, ctx.version.as_str()), (1_735_689_600, false, "abc123"));
+        assert_eq!(ctx.root, root);
+        assert_eq!(ctx.year(), 2025);
+        assert_eq!(ctx.timestamp(), "2025-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn from_env_map_without_a_time_falls_back_to_the_clock() {
+        let ctx = BuildContext::from_env_map(&no_repo("clock"), &BTreeMap::new()).unwrap();
+        assert!(ctx.clock);
+        assert!(ctx.time > 1_700_000_000);
+        assert_eq!(ctx.version, "dev");
+        assert!(ctx.git.is_none());
+    }
+
+    #[test]
+    fn from_env_map_rejects_a_bad_source_date_epoch() {
+        let e = BuildContext::from_env_map(&no_repo("bad"), &env(&[("SOURCE_DATE_EPOCH", "yesterday")])).unwrap_err();
+        assert_eq!(e.exit_code(), 3);
+        assert_eq!(e.to_string(), "SOURCE_DATE_EPOCH `yesterday` is not a UNIX timestamp");
+    }
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+}
@@ -0,0 +1,286 @@
+/*
+ * FileName:        error
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 20:31:07
+ * LastModified:    2026-10-19 20:31:07
+ * Reference:       8ucchiman.github.io
+ * Description:     ビルドのエラー（種類ごとの終了コードと、rustc 風の表示）
+ */
+
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use crate::toml;
+
+/// ビルドが失敗した理由
+#[derive(Debug)]
+pub enum BuildError {
+    /// 設定の誤り。`file` は taxonomy.toml・Cargo.toml など（サイトの設定そのものなら None）。
+    /// `line` が 0 でなければ `source` の該当行を添えて表示する
+    Config { file: Option<PathBuf>, source: String, line: usize, col: usize, message: String },
+    /// ページの組み立ての誤り（ナビのリンク先が無いなど）
+    Template { page: String, message: String },
+    /// 設定が参照しているファイルが無い
+    MissingAsset { path: PathBuf, referenced_by: String },
+    /// URL として使えない値
+    InvalidUrl { url: String, field: String, message: String },
+    /// 読み書きの失敗
+    Io { path: PathBuf, source: io::Error },
+    /// `--deny warnings` で lint の警告を止めた
+    Lint { count: usize },
+    /// `--verify-reproducible` で 2 回の描画結果が違った
+    NotReproducible { paths: Vec<PathBuf> },
+    /// 原因に「何をしていたか」を付けたもの
+    Context { message: String, cause: Box<BuildError> },
+}
+
+impl BuildError {
+    /// ファイルを伴わない設定の誤り
+    pub fn config(message: impl Into<String>) -> BuildError {
+        BuildError::Config { file: None, source: String::new(), line: 0, col: 0, message: message.into() }
+    }
+
+    /// `file`（中身は `source`）の TOML のエラー
+    pub(crate) fn toml(file: &Path, source: &str, e: toml::Error) -> BuildError {
+        BuildError::Config { file: Some(file.to_path_buf()), source: source.to_string(), line: e.line, col: e.col, message: e.msg }
+    }
+
+    pub fn io(path: &Path, source: io::Error) -> BuildError {
+        BuildError::Io { path: path.to_path_buf(), source }
+    }
+
+    /// 何をしていたかを付ける（表示では `= note: while ...` になる）
+    pub fn context(self, message: impl Into<String>) -> BuildError {
+        BuildError::Context { message: message.into(), cause: Box::new(self) }
+    }
+
+    /// プロセスの終了コード（2 は CLI の使い方の誤りに使う）
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            BuildError::Config { .. } => 3,
+            BuildError::Template { .. } => 4,
+            BuildError::MissingAsset { .. } => 5,
+            BuildError::InvalidUrl { .. } => 6,
+            BuildError::Io { .. } => 7,
+            BuildError::Lint { .. } => 8,
+            BuildError::NotReproducible { .. } => 9,
+            BuildError::Context { cause, .. } => cause.exit_code(),
+        }
+    }
+
+    /// `error[<code>]` の code
+    fn code(&self) -> &'static str {
+        match self {
+            BuildError::Config { .. } => "config",
+            BuildError::Template { .. } => "template",
+            BuildError::MissingAsset { .. } => "missing-asset",
+            BuildError::InvalidUrl { .. } => "invalid-url",
+            BuildError::Io { .. } => "io",
+            BuildError::Lint { .. } => "lint",
+            BuildError::NotReproducible { .. } => "not-reproducible",
+            BuildError::Context { cause, .. } => cause.code(),
+        }
+    }
+
+    /// rustc 風の複数行の表示（末尾に改行を含む）
+    ///
+    /// ```text
+    /// error[config]: expected `=` after key
+    ///  --> taxonomy.toml:3:7
+    ///   |
+    /// 3 | label "3D"
+    ///   |       ^
+    ///   |
+    ///   = note: while importing `../pathtracer`
+    /// ```
+    pub fn render(&self) -> String {
+        // Context を剥がして、外側から順に note にする
+        let mut notes = Vec::new();
+        let mut e = self;
+        while let BuildError::Context { message, cause } = e {
+            notes.push(format!("while {message}"));
+            e = cause;
+        }
+
+        let mut out = format!("error[{}]: {}\n", e.code(), e.headline());
+        match e {
+            BuildError::Config { file: Some(file), source, line, col, .. } if *line > 0 => {
+                out.push_str(&snippet(file, source, *line, *col));
+            }
+            BuildError::Config { file: Some(file), .. } => out.push_str(&format!(" --> {}\n", file.display())),
+            BuildError::Io { path, source } => {
+                out.push_str(&format!(" --> {}\n", path.display()));
+                let mut cause = std::error::Error::source(source);
+                while let Some(c) = cause {
+                    notes.push(format!("caused by: {c}"));
+                    cause = c.source();
+                }
+            }
+            BuildError::MissingAsset { path, .. } => out.push_str(&format!(" --> {}\n", path.display())),
+            BuildError::NotReproducible { paths } => {
+                notes.extend(paths.iter().map(|p| format!("differs: {}", p.display())));
+                notes.push("help: look for HashMap iteration, the current time or absolute paths in the output".to_string());
+            }
+            _ => {}
+        }
+        for n in notes {
+            out.push_str(&format!("  = {}\n", note_label(&n)));
+        }
+        out
+    }
+
+    /// 1 行目に出す文（場所は含めない）
+    fn headline(&self) -> String {
+        match self {
+            BuildError::Config { message, .. } => message.clone(),
+            BuildError::Template { page, message } => format!("{page}: {message}"),
+            BuildError::MissingAsset { referenced_by, .. } => format!("{referenced_by} refers to a file that does not exist"),
+            BuildError::InvalidUrl { url, field, message } => format!("{field}: `{url}` {message}"),
+            BuildError::Io { source, .. } => source.to_string(),
+            BuildError::Lint { count } => format!("{count} lint warning(s) with --deny warnings"),
+            BuildError::NotReproducible { paths } => {
+                format!("the build is not reproducible ({} file(s) differ between two renders)", paths.len())
+            }
+            BuildError::Context { cause, .. } => cause.headline(),
+        }
+    }
+}
+
+/// note の先頭が `help:` / `caused by:` などならそのまま、それ以外は `note:` を付ける
+fn note_label(n: &str) -> String {
+    if n.starts_with("help:") || n.starts_with("caused by:") { n.to_string() } else { format!("note: {n}") }
+}
+
+/// ` --> file:line:col` と該当行、列を指す `^`
+fn snippet(file: &Path, source: &str, line: usize, col: usize) -> String {
+    let text = source.lines().nth(line - 1).unwrap_or_default();
+    let gutter = " ".repeat(line.to_string().len());
+    // タブはそのまま残して、^ の位置がずれないようにする
+    let pad: String = text.chars().take(col.saturating_sub(1)).map(|c| if c == '\t' { '\t' } else { ' ' }).collect();
+    format!(
+        "{gutter}--> {}:{line}:{col}\n{gutter} |\n{line} | {text}\n{gutter} | {pad}^\n{gutter} |\n",
+        file.display(),
+    )
+}
+
+impl fmt::Display for BuildError {
+    /// 1 行の表示（`file:line:col: message`）。詳しくは render()
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Config { file: Some(file), line, col, message, .. } if *line > 0 => {
+                write!(f, "{}:{line}:{col}: {message}", file.display())
+            }
+            BuildError::Config { file: Some(file), message, .. } => write!(f, "{}: {message}", file.display()),
+            BuildError::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            BuildError::MissingAsset { path, referenced_by } => write!(f, "{}: does not exist (referenced by {referenced_by})", path.display()),
+            BuildError::Context { message, cause } => write!(f, "while {message}: {cause}"),
+            e => f.write_str(&e.headline()),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::Io { source, .. } => Some(source),
+            BuildError::Context { cause, .. } => Some(cause.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// `fs::read(&p).at(&p)?` のように io のエラーにパスを付ける
+pub(crate) trait IoResultExt<T> {
+    fn at(self, path: &Path) -> Result<T, BuildError>;
+}
+
+impl<T> IoResultExt<T> for io::Result<T> {
+    fn at(self, path: &Path) -> Result<T, BuildError> {
+        self.map_err(|e| BuildError::io(path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toml_error(src: &str) -> BuildError {
+        BuildError::toml(Path::new("taxonomy.toml"), src, toml::parse(src).unwrap_err())
+    }
+
+    #[test]
+    fn toml_errors_point_at_the_line_and_column() {
+        let e = toml_error("[tags.opengl]\nparent = \"3d\"\nlabel \"3D\"\n");
+        assert_eq!(e.to_string(), "taxonomy.toml:3:7: expected `=` after key");
+        assert_eq!(
+            e.render(),
+            "error[config]: expected `=` after key\n --> taxonomy.toml:3:7\n  |\n3 | label \"3D\"\n  |       ^\n  |\n"
+        );
+    }
+
+    #[test]
+    fn snippet_keeps_tabs_and_widens_the_gutter() {
+        let source = format!("{}\tkey \"x\"\n", "\n".repeat(9));
+        assert_eq!(
+            snippet(Path::new("a.toml"), &source, 10, 6),
+            "  --> a.toml:10:6\n   |\n10 | \tkey \"x\"\n   | \t    ^\n   |\n"
+        );
+    }
+
+    /// 原因を持つエラー（io::Error の source() はこの source() を返す）
+    #[derive(Debug)]
+    struct Wrapped(io::Error);
+
+    impl fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("cannot read the manifest")
+        }
+    }
+
+    impl std::error::Error for Wrapped {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn contexts_become_notes_from_the_outside_in() {
+        let inner = io::Error::other(Wrapped(io::Error::other("disk on fire")));
+        let e = BuildError::io(Path::new("../pathtracer/Cargo.toml"), inner)
+            .context("reading `../pathtracer/Cargo.toml`")
+            .context("importing `../pathtracer`");
+        assert_eq!(e.exit_code(), 7);
+        assert_eq!(
+            e.render(),
+            "error[io]: cannot read the manifest\n --> ../pathtracer/Cargo.toml\n\
+             \x20 = note: while importing `../pathtracer`\n\
+             \x20 = note: while reading `../pathtracer/Cargo.toml`\n\
+             \x20 = caused by: disk on fire\n"
+        );
+        assert_eq!(
+            e.to_string(),
+            "while importing `../pathtracer`: while reading `../pathtracer/Cargo.toml`: ../pathtracer/Cargo.toml: cannot read the manifest"
+        );
+    }
+
+    #[test]
+    fn exit_codes_per_kind() {
+        let errors = [
+            (BuildError::config("x"), 3),
+            (BuildError::Template { page: "index.html".into(), message: "x".into() }, 4),
+            (BuildError::MissingAsset { path: "a.png".into(), referenced_by: "about.portrait".into() }, 5),
+            (BuildError::InvalidUrl { url: "x".into(), field: "site_url".into(), message: "x".into() }, 6),
+            (BuildError::io(Path::new("a"), io::ErrorKind::NotFound.into()), 7),
+            (BuildError::Lint { count: 2 }, 8),
+            (BuildError::NotReproducible { paths: vec!["index.html".into()] }, 9),
+        ];
+        for (e, code) in errors {
+            assert_eq!(e.exit_code(), code, "{e}");
+            // Context で包んでも変わらない
+            assert_eq!(e.context("building").exit_code(), code);
+        }
+    }
+}
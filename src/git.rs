@@ -0,0 +1,588 @@
+/*
+ * FileName:        git
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 19:40:18
+ * LastModified:    2026-10-19 19:40:18
+ * Reference:       8ucchiman.github.io
+ * Description:     .git を直接読んで、今のコミット・その時刻・作業ツリーが汚れているかを調べる
+ *
+ * HEAD → refs（loose / packed-refs）→ コミットオブジェクト（loose / pack、delta も展開）の順に読む。
+ * 汚れているかは index と作業ツリー（stat が違えば blob ハッシュ）、index と HEAD のツリーを比べる。
+ * 読めない形式（SHA-256 のリポジトリ、非 UTF-8 のパスなど）のときだけ git コマンドに頼る。
+ */
+
+
+use std::{
+    cell::OnceCell,
+    collections::BTreeMap,
+    fs,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    process::Command,
+    time::UNIX_EPOCH,
+};
+
+use crate::{inflate, sha1};
+
+/// ビルドしたコミットの情報
+#[derive(Clone, Debug, PartialEq)]
+pub struct GitInfo {
+    /// コミットのハッシュ（16 進 40 桁）
+    pub commit: String,
+    /// コミット時刻（committer の UNIX 秒）。コミットが読めなければ None
+    pub time: Option<u64>,
+    /// 作業ツリーか index に、コミットされていない変更がある（未追跡のファイルは数えない）
+    pub dirty: bool,
+}
+
+impl GitInfo {
+    /// `root` か、その祖先にあるリポジトリを読む。リポジトリでない・まだコミットが無いなら None
+    pub fn read(root: &Path) -> Option<GitInfo> {
+        let repo = Repo::find(root)?;
+        if repo.sha256() {
+            return GitInfo::from_command(root);
+        }
+        let commit = repo.head()?;
+        let time = repo.commit_time(&commit).or_else(|| command(root, &["log", "-1", "--format=%ct"])?.parse().ok());
+        let dirty = match repo.dirty(&commit) {
+            Some(dirty) => dirty,
+            None => command(root, &["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty()),
+        };
+        Some(GitInfo { commit, time, dirty })
+    }
+
+    /// git コマンドで調べる（.git を読めないときの代わり）
+    fn from_command(root: &Path) -> Option<GitInfo> {
+        let commit = command(root, &["rev-parse", "HEAD"])?;
+        let time = command(root, &["log", "-1", "--format=%ct"]).and_then(|s| s.parse().ok());
+        let dirty = command(root, &["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty());
+        Some(GitInfo { commit, time, dirty })
+    }
+
+    /// 短いハッシュ（12 桁）
+    pub fn short(&self) -> &str {
+        &self.commit[..self.commit.len().min(12)]
+    }
+}
+
+/// `git -C root <args>` の標準出力（前後の空白を除く）。git が無い・失敗したら None
+fn command(root: &Path, args: &[&str]) -> Option<String> {
+    let out = Command::new("git").arg("-C").arg(root).args(args).output().ok()?;
+    out.status.success().then(|| String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+struct Repo {
+    /// HEAD と index がある場所（worktree なら .git/worktrees/<name>）
+    git_dir: PathBuf,
+    /// refs・packed-refs・objects がある場所
+    common_dir: PathBuf,
+    work_tree: PathBuf,
+    /// objects/pack/*.idx（最初に pack を探すときに読む）
+    packs: OnceCell<Vec<Pack>>,
+}
+
+impl Repo {
+    fn find(root: &Path) -> Option<Repo> {
+        let start = root.canonicalize().ok()?;
+        for dir in start.ancestors() {
+            let dot = dir.join(".git");
+            let git_dir = if dot.is_dir() {
+                dot
+            } else if dot.is_file() {
+                // worktree・submodule は `gitdir: <path>` を書いたファイル
+                let text = fs::read_to_string(&dot).ok()?;
+                dir.join(text.trim().strip_prefix("gitdir:")?.trim())
+            } else {
+                continue;
+            };
+            let common_dir = match fs::read_to_string(git_dir.join("commondir")) {
+                Ok(rel) => git_dir.join(rel.trim()),
+                Err(_) => git_dir.clone(),
+            };
+            return Some(Repo { git_dir, common_dir, work_tree: dir.to_path_buf(), packs: OnceCell::new() });
+        }
+        None
+    }
+
+    /// `extensions.objectFormat = sha256` のリポジトリか
+    fn sha256(&self) -> bool {
+        fs::read_to_string(self.common_dir.join("config"))
+            .is_ok_and(|c| c.lines().any(|l| l.trim().to_ascii_lowercase().replace(' ', "") == "objectformat=sha256"))
+    }
+
+    /// HEAD が指すコミット。ブランチにまだコミットが無ければ None
+    fn head(&self) -> Option<String> {
+        let mut target = fs::read_to_string(self.git_dir.join("HEAD")).ok()?.trim().to_string();
+        // シンボリック参照は何段か辿る（ループしていたら諦める）
+        for _ in 0..5 {
+            let Some(name) = target.strip_prefix("ref:") else {
+                return is_hash(&target).then_some(target);
+            };
+            target = self.read_ref(name.trim())?;
+        }
+        None
+    }
+
+    /// loose な ref（worktree 固有 → 共通）→ packed-refs の順に探す
+    fn read_ref(&self, name: &str) -> Option<String> {
+        for dir in [&self.git_dir, &self.common_dir] {
+            if let Ok(text) = fs::read_to_string(dir.join(name)) {
+                return Some(text.trim().to_string());
+            }
+        }
+        let packed = fs::read_to_string(self.common_dir.join("packed-refs")).ok()?;
+        packed
+            .lines()
+            .filter(|l| !l.starts_with('#') && !l.starts_with('^'))
+            .find_map(|l| l.split_once(' ').filter(|(_, r)| r.trim() == name).map(|(hash, _)| hash.to_string()))
+    }
+
+    fn commit_time(&self, commit: &str) -> Option<u64> {
+        let (kind, body) = self.object(commit)?;
+        if kind != OBJ_COMMIT {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&body);
+        // `committer Name <mail> 1700000000 +0900`（ヘッダは最初の空行まで）
+        let line = text.lines().take_while(|l| !l.is_empty()).find(|l| l.starts_with("committer "))?;
+        line.rsplit(' ').nth(1)?.parse().ok()
+    }
+
+    /// オブジェクトの種類と中身（loose → pack の順に探す）
+    fn object(&self, hash: &str) -> Option<(u8, Vec<u8>)> {
+        if !is_hash(hash) {
+            return None;
+        }
+        let loose = self.common_dir.join("objects").join(&hash[..2]).join(&hash[2..]);
+        if let Ok(data) = fs::read(&loose) {
+            let raw = inflate::zlib(&data)?;
+            let nul = raw.iter().position(|&b| b == 0)?;
+            let header = std::str::from_utf8(&raw[..nul]).ok()?;
+            let kind = match header.split(' ').next()? {
+                "commit" => OBJ_COMMIT,
+                "tree" => OBJ_TREE,
+                "blob" => OBJ_BLOB,
+                "tag" => OBJ_TAG,
+                _ => return None,
+            };
+            return Some((kind, raw[nul + 1..].to_vec()));
+        }
+        let id = unhex(hash)?;
+        self.packs.get_or_init(|| self.open_packs()).iter().find_map(|pack| pack.find(&id).and_then(|offset| pack.entry(self, offset, 0)))
+    }
+
+    fn open_packs(&self) -> Vec<Pack> {
+        let dir = self.common_dir.join("objects/pack");
+        let mut idx: Vec<PathBuf> = match fs::read_dir(&dir) {
+            Ok(rd) => rd.filter_map(|e| e.ok().map(|e| e.path())).filter(|p| p.extension().is_some_and(|e| e == "idx")).collect(),
+            Err(_) => Vec::new(),
+        };
+        idx.sort();
+        idx.iter().filter_map(|p| Pack::open(p)).collect()
+    }
+
+    /// コミットされていない変更があるか。index が読めなければ None
+    fn dirty(&self, commit: &str) -> Option<bool> {
+        let index_path = self.git_dir.join("index");
+        let data = fs::read(&index_path).ok()?;
+        let index_mtime = fs::metadata(&index_path).ok()?.modified().ok()?;
+        let entries = parse_index(&data)?;
+
+        // index と作業ツリー
+        for e in &entries {
+            if e.stage != 0 {
+                return Some(true); // 競合中
+            }
+            if e.skip_worktree || e.mode == MODE_GITLINK {
+                continue;
+            }
+            let path = self.work_tree.join(std::str::from_utf8(&e.path).ok()?);
+            let Ok(meta) = fs::symlink_metadata(&path) else {
+                return Some(true); // 消されている
+            };
+            if meta.len() as u32 != e.size {
+                return Some(true);
+            }
+            let modified = meta.modified().ok()?;
+            let mtime = modified.duration_since(UNIX_EPOCH).ok()?;
+            // index を書いた後に触られたもの（racy）は stat が同じでも中身を比べる
+            if mtime.as_secs() as u32 == e.mtime.0 && mtime.subsec_nanos() == e.mtime.1 && modified < index_mtime {
+                continue;
+            }
+            let content = if meta.file_type().is_symlink() {
+                fs::read_link(&path).ok()?.to_str()?.as_bytes().to_vec()
+            } else {
+                fs::read(&path).ok()?
+            };
+            let mut blob = format!("blob {}\0", content.len()).into_bytes();
+            blob.extend_from_slice(&content);
+            if sha1::digest(&blob) != e.id {
+                return Some(true);
+            }
+        }
+
+        // index と HEAD のツリー（ステージ済みの変更）
+        let (kind, body) = self.object(commit)?;
+        if kind != OBJ_COMMIT {
+            return None;
+        }
+        let tree = String::from_utf8_lossy(&body).lines().next()?.strip_prefix("tree ")?.to_string();
+        let mut head = BTreeMap::new();
+        self.flatten_tree(&tree, Vec::new(), &mut head)?;
+        let index: BTreeMap<Vec<u8>, (u32, [u8; 20])> = entries.into_iter().map(|e| (e.path, (e.mode, e.id))).collect();
+        Some(head != index)
+    }
+
+    /// ツリーを「パス → (mode, ハッシュ)」に平らにする
+    fn flatten_tree(&self, hash: &str, prefix: Vec<u8>, out: &mut BTreeMap<Vec<u8>, (u32, [u8; 20])>) -> Option<()> {
+        let (kind, body) = self.object(hash)?;
+        if kind != OBJ_TREE {
+            return None;
+        }
+        // `<mode 8 進> <name>\0<20 バイト>` の繰り返し
+        let mut rest = body.as_slice();
+        while !rest.is_empty() {
+            let sp = rest.iter().position(|&b| b == b' ')?;
+            let nul = rest.iter().position(|&b| b == 0)?;
+            let mode = u32::from_str_radix(std::str::from_utf8(&rest[..sp]).ok()?, 8).ok()?;
+            let mut path = prefix.clone();
+            path.extend_from_slice(&rest[sp + 1..nul]);
+            let id: [u8; 20] = rest.get(nul + 1..nul + 21)?.try_into().ok()?;
+            rest = &rest[nul + 21..];
+            if mode == MODE_TREE {
+                path.push(b'/');
+                self.flatten_tree(&sha1::hex(&id), path, out)?;
+            } else {
+                out.insert(path, (mode, id));
+            }
+        }
+        Some(())
+    }
+}
+
+const OBJ_COMMIT: u8 = 1;
+const OBJ_TREE: u8 = 2;
+const OBJ_BLOB: u8 = 3;
+const OBJ_TAG: u8 = 4;
+const OBJ_OFS_DELTA: u8 = 6;
+const OBJ_REF_DELTA: u8 = 7;
+const MODE_TREE: u32 = 0o040000;
+const MODE_GITLINK: u32 = 0o160000;
+/// delta の連鎖をこれより深く辿らない
+const MAX_DELTA_DEPTH: usize = 64;
+
+/// pack（.idx v2 と .pack）
+struct Pack {
+    pack: PathBuf,
+    idx: Vec<u8>,
+    count: usize,
+    /// 全エントリの先頭位置（昇順）と pack の末尾。エントリの終わりを知るのに使う
+    bounds: Vec<u64>,
+}
+
+impl Pack {
+    fn open(idx_path: &Path) -> Option<Pack> {
+        let idx = fs::read(idx_path).ok()?;
+        if idx.get(..8)? != b"\xfftOc\0\0\0\x02" {
+            return None;
+        }
+        let count = be32(&idx, 8 + 255 * 4)? as usize;
+        let pack = idx_path.with_extension("pack");
+        let len = fs::metadata(&pack).ok()?.len();
+        let mut p = Pack { pack, idx, count, bounds: Vec::new() };
+        let mut bounds: Vec<u64> = (0..count).map(|i| p.offset(i)).collect::<Option<_>>()?;
+        bounds.push(len.checked_sub(20)?); // 末尾は pack 全体のチェックサム
+        bounds.sort_unstable();
+        p.bounds = bounds;
+        Some(p)
+    }
+
+    /// i 番目のオブジェクトの pack 内の位置
+    fn offset(&self, i: usize) -> Option<u64> {
+        let offsets = 8 + 256 * 4 + self.count * 24;
+        let v = be32(&self.idx, offsets + i * 4)?;
+        if v & 0x8000_0000 == 0 {
+            return Some(u64::from(v));
+        }
+        let large = offsets + self.count * 4 + (v & 0x7fff_ffff) as usize * 8;
+        Some(u64::from(be32(&self.idx, large)?) << 32 | u64::from(be32(&self.idx, large + 4)?))
+    }
+
+    fn find(&self, id: &[u8; 20]) -> Option<u64> {
+        let fanout = |b: usize| be32(&self.idx, 8 + b * 4).map(|n| n as usize);
+        let lo = if id[0] == 0 { 0 } else { fanout(usize::from(id[0]) - 1)? };
+        let hi = fanout(usize::from(id[0]))?;
+        let names = 8 + 256 * 4;
+        let (mut lo, mut hi) = (lo, hi);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            match self.idx.get(names + mid * 20..names + mid * 20 + 20)?.cmp(&id[..]) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return self.offset(mid),
+            }
+        }
+        None
+    }
+
+    /// `offset` にあるエントリを展開する（delta なら元のオブジェクトに当てる）
+    fn entry(&self, repo: &Repo, offset: u64, depth: usize) -> Option<(u8, Vec<u8>)> {
+        if depth > MAX_DELTA_DEPTH {
+            return None;
+        }
+        let end = *self.bounds.iter().find(|&&b| b > offset)?;
+        let mut buf = vec![0u8; (end - offset) as usize];
+        let mut file = fs::File::open(&self.pack).ok()?;
+        file.seek(SeekFrom::Start(offset)).ok()?;
+        file.read_exact(&mut buf).ok()?;
+
+        // 種類（3 ビット）とサイズの可変長ヘッダ
+        let mut i = 0;
+        let mut c = *buf.first()?;
+        let kind = (c >> 4) & 7;
+        while c & 0x80 != 0 {
+            i += 1;
+            c = *buf.get(i)?;
+        }
+        i += 1;
+        match kind {
+            OBJ_COMMIT..=OBJ_TAG => Some((kind, inflate::zlib(&buf[i..])?)),
+            OBJ_OFS_DELTA => {
+                // 負のオフセット（各バイトの続きがあるたびに +1 する独特の符号化）
+                let mut c = *buf.get(i)?;
+                i += 1;
+                let mut back = u64::from(c & 0x7f);
+                while c & 0x80 != 0 {
+                    c = *buf.get(i)?;
+                    i += 1;
+                    back = ((back + 1) << 7) | u64::from(c & 0x7f);
+                }
+                let (base_kind, base) = self.entry(repo, offset.checked_sub(back)?, depth + 1)?;
+                Some((base_kind, apply_delta(&base, &inflate::zlib(&buf[i..])?)?))
+            }
+            OBJ_REF_DELTA => {
+                let base_id = buf.get(i..i + 20)?;
+                let (base_kind, base) = repo.object(&sha1::hex(base_id))?;
+                Some((base_kind, apply_delta(&base, &inflate::zlib(&buf[i + 20..])?)?))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// git の delta（元のサイズ・結果のサイズ・コピー/挿入命令の列）を当てる
+fn apply_delta(base: &[u8], delta: &[u8]) -> Option<Vec<u8>> {
+    let varint = |i: &mut usize| -> Option<usize> {
+        let (mut v, mut shift) = (0usize, 0);
+        loop {
+            let c = *delta.get(*i)?;
+            *i += 1;
+            v |= usize::from(c & 0x7f) << shift;
+            shift += 7;
+            if c & 0x80 == 0 {
+                return Some(v);
+            }
+        }
+    };
+    let mut i = 0;
+    if varint(&mut i)? != base.len() {
+        return None;
+    }
+    let size = varint(&mut i)?;
+    let mut out = Vec::with_capacity(size);
+    while i < delta.len() {
+        let op = delta[i];
+        i += 1;
+        if op & 0x80 != 0 {
+            // コピー: オフセット 4 バイト・長さ 3 バイトのうち、立っているビットのものだけ続く
+            let (mut off, mut len) = (0usize, 0usize);
+            for k in 0..4 {
+                if op & (1 << k) != 0 {
+                    off |= usize::from(*delta.get(i)?) << (8 * k);
+                    i += 1;
+                }
+            }
+            for k in 0..3 {
+                if op & (0x10 << k) != 0 {
+                    len |= usize::from(*delta.get(i)?) << (8 * k);
+                    i += 1;
+                }
+            }
+            if len == 0 {
+                len = 0x10000;
+            }
+            out.extend_from_slice(base.get(off..off + len)?);
+        } else if op != 0 {
+            // 挿入
+            out.extend_from_slice(delta.get(i..i + usize::from(op))?);
+            i += usize::from(op);
+        } else {
+            return None;
+        }
+    }
+    (out.len() == size).then_some(out)
+}
+
+struct IndexEntry {
+    path: Vec<u8>,
+    mode: u32,
+    id: [u8; 20],
+    size: u32,
+    /// (秒, ナノ秒)
+    mtime: (u32, u32),
+    stage: u16,
+    skip_worktree: bool,
+}
+
+/// .git/index（DIRC v2〜v4）のエントリ
+fn parse_index(data: &[u8]) -> Option<Vec<IndexEntry>> {
+    if data.get(..4)? != b"DIRC" {
+        return None;
+    }
+    let version = be32(data, 4)?;
+    if !(2..=4).contains(&version) {
+        return None;
+    }
+    let count = be32(data, 8)? as usize;
+    let mut pos = 12;
+    let mut prev: Vec<u8> = Vec::new();
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        let e = data.get(pos..pos + 62)?;
+        let flags = u16::from_be_bytes([e[60], e[61]]);
+        let mut p = pos + 62;
+        let mut skip_worktree = false;
+        if flags & 0x4000 != 0 {
+            // 拡張フラグ（v3 以降）
+            let extra = u16::from_be_bytes(data.get(p..p + 2)?.try_into().ok()?);
+            skip_worktree = extra & 0x4000 != 0;
+            p += 2;
+        }
+        let path = if version == 4 {
+            // 前のパスの末尾 N バイトを落として続きを足す
+            let mut strip = 0usize;
+            let mut c = *data.get(p)?;
+            p += 1;
+            strip |= usize::from(c & 0x7f);
+            while c & 0x80 != 0 {
+                c = *data.get(p)?;
+                p += 1;
+                strip = ((strip + 1) << 7) | usize::from(c & 0x7f);
+            }
+            let nul = p + data.get(p..)?.iter().position(|&b| b == 0)?;
+            prev.truncate(prev.len().checked_sub(strip)?);
+            prev.extend_from_slice(&data[p..nul]);
+            p = nul + 1;
+            prev.clone()
+        } else {
+            // NUL で終わり、エントリ全体が 8 バイトの倍数になるまで NUL で埋める
+            let nul = p + data.get(p..)?.iter().position(|&b| b == 0)?;
+            let path = data[p..nul].to_vec();
+            p = pos + (nul - pos + 8) / 8 * 8;
+            path
+        };
+        entries.push(IndexEntry {
+            path,
+            mode: be32(e, 24)?,
+            id: e[40..60].try_into().ok()?,
+            size: be32(e, 36)?,
+            mtime: (be32(e, 8)?, be32(e, 12)?),
+            stage: (flags >> 12) & 3,
+            skip_worktree,
+        });
+        pos = p;
+    }
+    Some(entries)
+}
+
+fn be32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn is_hash(s: &str) -> bool {
+    s.len() == 40 && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn unhex(s: &str) -> Option<[u8; 20]> {
+    let mut out = [0u8; 20];
+    for (i, b) in out.iter_mut().enumerate() {
+        *b = u8::from_str_radix(s.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn apply_delta_copies_and_inserts() {
+        let base = b"hello, world";
+        let delta = [
+            12, 12,                   // 元のサイズ・結果のサイズ
+            0x91, 7, 5,               // コピー: オフセット 7・長さ 5
+            2, b',', b' ',            // 挿入: 2 バイト
+            0x90, 5,                  // コピー: オフセット 0（省略）・長さ 5
+        ];
+        assert_eq!(apply_delta(base, &delta).unwrap(), b"world, hello");
+        // 元のサイズ・結果のサイズが合わない、範囲外のコピー、命令 0 は壊れている
+        assert_eq!(apply_delta(b"hello", &delta), None);
+        assert_eq!(apply_delta(base, &[12, 13, 0x91, 7, 5, 2, b',', b' ', 0x90, 5]), None);
+        assert_eq!(apply_delta(base, &[12, 5, 0x91, 10, 5]), None);
+        assert_eq!(apply_delta(base, &[12, 0, 0]), None);
+    }
+
+    #[test]
+    fn apply_delta_reads_multi_byte_sizes() {
+        let base = vec![b'x'; 200];
+        // 200 = 0xc8 → varint c8 01。長さ 0 のコピーは 0x10000 バイトなので、ここでは 200 を明示する
+        let delta = [0xc8, 0x01, 0xc9, 0x01, 0x90, 200, 1, b'!'];
+        let out = apply_delta(&base, &delta).unwrap();
+        assert_eq!(out.len(), 201);
+        assert_eq!(out.last(), Some(&b'!'));
+    }
+
+    // `git add a.txt dir/b.txt dir/c.txt` した直後の .git/index（v2）と、
+    // それを `git update-index --index-version 4` したもの（パスの前方一致を省く）
+    const INDEX_V2: &str = "4449524300000002000000036ad5698d384143db6ad5698d384143db0000fe000012e17d000081a4000000000000000000000006ce013625030ba8dba906f756967f9e9ca394464a0005612e74787400000000006ad5698d384143db6ad5698d384143db0000fe000012e17f000081a4000000000000000000000006cc628ccd10742baea8241c5924df992b5c019f7100096469722f622e747874006ad5698d384143db6ad5698d384143db0000fe000012e180000081a4000000000000000000000002587be6b4c3f93f93c489c0111bba5596147a26cb00096469722f632e747874004e1bb7ca0e0796a74e094ce5c9fefb311d70eb91";
+    const INDEX_V4: &str = "4449524300000004000000036ad5698d384143db6ad5698d384143db0000fe000012e17d000081a4000000000000000000000006ce013625030ba8dba906f756967f9e9ca394464a000500612e747874006ad5698d384143db6ad5698d384143db0000fe000012e17f000081a4000000000000000000000006cc628ccd10742baea8241c5924df992b5c019f710009056469722f622e747874006ad5698d384143db6ad5698d384143db0000fe000012e180000081a4000000000000000000000002587be6b4c3f93f93c489c0111bba5596147a26cb000905632e74787400c92bf154450fcf66c197f5d2006e05ee8d327ba5";
+
+    fn check_index(entries: &[IndexEntry]) {
+        let want = [
+            ("a.txt", "ce013625030ba8dba906f756967f9e9ca394464a", 6),
+            ("dir/b.txt", "cc628ccd10742baea8241c5924df992b5c019f71", 6),
+            ("dir/c.txt", "587be6b4c3f93f93c489c0111bba5596147a26cb", 2),
+        ];
+        assert_eq!(entries.len(), want.len());
+        for (e, (path, id, size)) in entries.iter().zip(want) {
+            assert_eq!(e.path, path.as_bytes());
+            assert_eq!(sha1::hex(&e.id), id);
+            assert_eq!(e.size, size);
+            assert_eq!(e.mode, 0o100644);
+            assert_eq!(e.mtime, (0x6ad5698d, 0x384143db));
+            assert_eq!((e.stage, e.skip_worktree), (0, false));
+        }
+    }
+
+    #[test]
+    fn parse_index_v2() {
+        check_index(&parse_index(&bytes(INDEX_V2)).unwrap());
+    }
+
+    #[test]
+    fn parse_index_v4() {
+        check_index(&parse_index(&bytes(INDEX_V4)).unwrap());
+    }
+
+    #[test]
+    fn parse_index_rejects_other_formats() {
+        let mut v5 = bytes(INDEX_V2);
+        v5[7] = 5;
+        assert!(parse_index(&v5).is_none());
+        assert!(parse_index(&bytes(INDEX_V2)[..100]).is_none());
+    }
+}
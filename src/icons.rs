@@ -0,0 +1,539 @@
+/*
+ * FileName:        icons
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 12:40:26
+ * LastModified:    2026-10-19 12:40:26
+ * Reference:       8ucchiman.github.io
+ * Description:     SVG アイコンの登録簿（組み込み + assets/icons/ の読み込みとサニタイズ）
+ */
+
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{BuildError, IoResultExt},
+    Files,
+};
+
+/// 組み込みアイコン（24x24、currentColor、塗りは既定の nonzero）。name → path の d 属性
+const BUILTIN: &[(&str, &str)] = &[
+    ("github", "M12 .5a12 12 0 0 0-3.79 23.39c.6.11.82-.26.82-.58v-2.14c-3.34.73-4.04-1.61-4.04-1.61-.55-1.39-1.35-1.76-1.35-1.76-1.1-.75.08-.74.08-.74 1.22.09 1.86 1.27 1.86 1.27 1.08 1.86 2.83 1.32 3.52 1.01.11-.78.42-1.32.76-1.62-2.66-.3-5.47-1.33-5.47-5.92 0-1.31.47-2.39 1.25-3.23-.13-.31-.54-1.56.12-3.25 0 0 1.01-.32 3.3 1.23a11.5 11.5 0 0 1 6 0c2.3-1.55 3.3-1.23 3.3-1.23.66 1.69.25 2.94.12 3.25.78.84 1.25 1.92 1.25 3.23 0 4.6-2.81 5.61-5.49 5.91.43.37.81 1.1.81 2.22v3.29c0 .32.21.7.82.58A12 12 0 0 0 12 .5z"),
+    ("linkedin", "M22.225 0H1.771C.792 0 0 .774 0 1.729v20.542C0 23.226.792 24 1.771 24h20.454C23.205 24 24 23.226 24 22.271V1.729C24 .774 23.205 0 22.225 0ZM6.75 20.452H3.92V9H6.75v11.452ZM5.337 7.433a2.062 2.062 0 1 1 0-4.124 2.062 2.062 0 0 1 0 4.124ZM20.447 20.452h-3.554V14.74c0-1.361-.027-3.112-1.897-3.112-1.898 0-2.189 1.48-2.189 3.007v5.817H9.254V9h3.414v1.561h.049c.476-.9 1.637-1.848 3.372-1.848 3.605 0 4.266 2.372 4.266 5.455v6.284Z"),
+    ("youtube", "M23.5 6.2a3.5 3.5 0 0 0-2.46-2.48C19.2 3.2 12 3.2 12 3.2s-7.2 0-9.04.52A3.5 3.5 0 0 0 .5 6.2 36.8 36.8 0 0 0 0 12c0 1.92.18 3.84.5 5.8a3.5 3.5 0 0 0 2.46 2.48C4.8 20.8 12 20.8 12 20.8s7.2 0 9.04-.52a3.5 3.5 0 0 0 2.46-2.48c.32-1.95.5-3.87.5-5.8 0-1.92-.18-3.84-.5-5.8ZM9.6 15.5V8.5L15.8 12l-6.2 3.5Z"),
+    ("blog", "M5 3h10a4 4 0 0 1 4 4v12a2 2 0 0 1-2 2H7V5a2 2 0 0 1-2-2Zm0 2v14h12V7a2 2 0 0 0-2-2H5Zm3 4h6v2H8V9Zm0 4h8v2H8v-2Z"),
+    ("mastodon", "M21.3 7.9c0-4.6-3-5.9-3-5.9C16.8 1.3 14.2 1 11.5 1h-.1C8.8 1 6.2 1.3 4.7 2c0 0-3 1.3-3 5.9 0 1-.1 2.3 0 3.6.1 4.5.8 8.9 5 10 1.9.5 3.6.6 4.9.5 2.5-.1 3.8-.9 3.8-.9l-.1-1.8s-1.7.6-3.7.5c-2 0-4-.2-4.4-2.6v-.7s1.9.5 4.4.6c1.5.1 2.9-.1 4.3-.2 2.7-.3 5.1-2 5.4-3.5.5-2.4.4-5.8.4-5.8ZM17.7 13.8h-2.3V8.3c0-1.2-.5-1.8-1.5-1.8-1.1 0-1.7.7-1.7 2.1v3h-2.3v-3c0-1.4-.6-2.1-1.7-2.1-1 0-1.5.6-1.5 1.8v5.5H4.4V8.1c0-1.2.3-2.1.9-2.8.6-.7 1.4-1 2.4-1 1.2 0 2 .4 2.6 1.3l.6 1 .6-1c.6-.9 1.4-1.3 2.6-1.3 1 0 1.8.3 2.4 1 .6.7.9 1.6.9 2.8v5.7Z"),
+    ("x", "M18.901 1.153h3.68l-8.04 9.19L24 22.846h-7.406l-5.8-7.584-6.638 7.584H.474l8.6-9.83L0 1.154h7.594l5.243 6.932ZM17.61 20.644h2.039L6.486 3.24H4.298Z"),
+    ("bluesky", "M5.2 2.7C7.9 4.7 10.8 8.8 12 11.1c1.2-2.3 4.1-6.4 6.8-8.4 2-1.5 5.2-2.6 5.2 1 0 .7-.4 6.1-.7 7-.8 3-3.9 3.8-6.6 3.3 4.8.8 6 3.5 3.4 6.2-5 5.1-7.2-1.3-7.7-2.9l-.2-.6-.2.6c-.5 1.6-2.7 8-7.7 2.9-2.6-2.7-1.4-5.4 3.4-6.2-2.7.5-5.8-.3-6.6-3.3C.4 9.8 0 4.4 0 3.7c0-3.6 3.2-2.5 5.2-1Z"),
+    ("zenn", "M.264 23.771h4.984c.264 0 .498-.147.645-.352L19.614.874c.176-.293-.029-.645-.381-.645h-4.72c-.235 0-.44.117-.557.323L.03 23.361c-.088.176.029.41.234.41ZM17.445 23.419l6.479-10.408c.205-.323-.029-.733-.41-.733h-4.691c-.176 0-.352.088-.44.235l-6.655 10.643c-.176.264.029.616.352.616h4.779c.234 0 .468-.118.586-.353Z"),
+    ("qiita", "M12 2a10 10 0 1 0 5.9 18.1l2.4 2.4 1.4-1.4-2.3-2.3A10 10 0 0 0 12 2Zm0 3a7 7 0 1 1 0 14 7 7 0 0 1 0-14Z"),
+    ("orcid", "M12 0a12 12 0 1 0 0 24 12 12 0 0 0 0-24ZM7.4 5.6a1.1 1.1 0 1 1 0 2.2 1.1 1.1 0 0 1 0-2.2ZM6.5 9.2h1.8v9.4H6.5V9.2Zm4.3 0h4.1c3.1 0 4.6 2.2 4.6 4.7 0 2.7-2.1 4.7-4.6 4.7h-4.1V9.2Zm1.8 1.6v6.2h2.1c2.3 0 3-1.6 3-3.1 0-1.7-1-3.1-3-3.1h-2.1Z"),
+    ("email", "M2 4h20a2 2 0 0 1 2 2v12a2 2 0 0 1-2 2H2a2 2 0 0 1-2-2V6a2 2 0 0 1 2-2Zm0 3.2V18h20V7.2l-10 6.3L2 7.2ZM3.4 6 12 11.4 20.6 6H3.4Z"),
+    ("link", "M10.6 13.4a1 1 0 0 1 0-1.4l3.5-3.5a1 1 0 1 1 1.4 1.4L12 13.4a1 1 0 0 1-1.4 0ZM7 21a4 4 0 0 1-2.8-6.8l3-3 1.4 1.4-3 3A2 2 0 1 0 8.4 18.4l3-3 1.4 1.4-3 3A4 4 0 0 1 7 21Zm9.8-8.2-1.4-1.4 3-3A2 2 0 1 0 15.6 5.6l-3 3-1.4-1.4 3-3a4 4 0 1 1 5.6 5.6l-3 3Z"),
+];
+
+/// 丸ごと落とす要素（中身も含めて）。<style> はページ全体に効いてしまうので落とす。
+/// アニメーション（<set attributeName="href" to="javascript:..."> など）は属性を後から書き換えられるので落とす
+const DROP_ELEMENTS: &[&str] = &[
+    "script", "style", "foreignobject", "iframe", "object", "embed", "audio", "video",
+    "set", "animate", "animatemotion", "animatetransform", "animatecolor", "discard",
+];
+
+/// タグだけ落として中身は残す要素（<a> の中の図形は残す）
+const UNWRAP_ELEMENTS: &[&str] = &["a"];
+
+/// dist 内のスプライトの置き場所
+pub const SPRITE: &str = "assets/icons.svg";
+
+#[derive(Clone)]
+pub struct Icon {
+    /// 正方形に揃えた viewBox
+    pub view_box: String,
+    /// <svg> の中身（サニタイズ・最適化済み）
+    pub body: String,
+}
+
+impl Icon {
+    /// ページに直接埋め込む <svg>
+    pub fn inline_html(&self) -> String {
+        format!(r#"<svg class="icon" viewBox="{}" aria-hidden="true">{}</svg>"#, self.view_box, self.body)
+    }
+}
+
+/// アイコンの登録簿。既定では `assets/icons.svg` のスプライトを `<use href>` で参照し、
+/// `inline` に挙げたもの（ファーストビューに出るものなど）だけページに直接埋め込む。
+pub struct Icons {
+    map: BTreeMap<String, Icon>,
+    inline: BTreeSet<String>,
+    /// スプライトに入れるもの（html() で使われた名前）
+    used: RefCell<BTreeSet<String>>,
+}
+
+impl Icons {
+    /// 組み込みアイコンに `dir/*.svg` を重ねる（同名はファイル側が優先）。ディレクトリが無ければ組み込みのみ。
+    pub fn load(dir: &Path) -> Result<Icons, BuildError> {
+        let mut map: BTreeMap<String, Icon> = BUILTIN
+            .iter()
+            .map(|(name, d)| {
+                let body = format!(r#"<path fill="currentColor" d="{d}"/>"#);
+                (name.to_string(), Icon { view_box: "0 0 24 24".to_string(), body: sanitize(&body, decimals(24.0)) })
+            })
+            .collect();
+        let mut files: Vec<_> = match fs::read_dir(dir) {
+            Ok(rd) => rd.collect::<io::Result<Vec<_>>>().at(dir)?.into_iter().map(|e| e.path()).collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(BuildError::io(dir, e)),
+        };
+        files.sort();
+        for path in files {
+            if path.extension().and_then(|e| e.to_str()) != Some("svg") {
+                continue;
+            }
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else { continue };
+            let src = fs::read_to_string(&path).at(&path)?;
+            match parse_svg(&src) {
+                Some(icon) => { map.insert(icon_name(stem), icon); }
+                None => eprintln!("warning: {}: no <svg> element, skipping", path.display()),
+            }
+        }
+        Ok(Icons { map, inline: BTreeSet::new(), used: RefCell::new(BTreeSet::new()) })
+    }
+
+    /// スプライトを使わず直接埋め込むアイコン
+    pub fn with_inline(mut self, names: &[&str]) -> Icons {
+        self.inline = names.iter().map(|n| n.to_ascii_lowercase()).collect();
+        self
+    }
+
+    /// その名前のアイコンがあるか（組み込みか assets/icons/）
+    pub fn contains(&self, name: &str) -> bool {
+        self.map.contains_key(&name.to_ascii_lowercase())
+    }
+
+    /// アイコンの <svg>。無い名前は None（呼び出し側で "link" などに落とす）。
+    /// inline 指定が無ければスプライトの `<use>`（`ver` はキャッシュバスター）
+    pub fn html(&self, name: &str, ver: &str) -> Option<String> {
+        let key = name.to_ascii_lowercase();
+        let icon = self.map.get(&key)?;
+        if self.inline.contains(&key) {
+            return Some(icon.inline_html());
+        }
+        self.used.borrow_mut().insert(key.clone());
+        Some(format!(
+            r#"<svg class="icon" viewBox="{}" aria-hidden="true"><use href="{SPRITE}?v={ver}#icon-{key}"/></svg>"#,
+            icon.view_box
+        ))
+    }
+
+    /// html() で参照されたアイコンを `<symbol id="icon-<name>">` にまとめて出力の `assets/icons.svg` に加える
+    pub fn write_sprite(&self, files: &mut Files) {
+        let used = self.used.borrow();
+        let mut svg = String::from(r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">"#);
+        for name in used.iter() {
+            let icon = &self.map[name];
+            svg.push_str(&format!(r#"<symbol id="icon-{name}" viewBox="{}">{}</symbol>"#, icon.view_box, icon.body));
+        }
+        svg.push_str("</svg>\n");
+        files.insert(PathBuf::from(SPRITE), svg.into_bytes());
+    }
+}
+
+/// ファイル名からアイコン名を作る。配布サイトの接尾辞（`linkedin-svgrepo-com` など）は落とす
+fn icon_name(stem: &str) -> String {
+    let stem = stem.to_ascii_lowercase();
+    for suffix in ["-svgrepo-com", "-icon", "-logo"] {
+        if let Some(s) = stem.strip_suffix(suffix)
+            && !s.is_empty()
+        {
+            return s.to_string();
+        }
+    }
+    stem
+}
+
+/// SVG ファイルを読み、ルートの属性から viewBox を決めて中身をサニタイズする
+fn parse_svg(src: &str) -> Option<Icon> {
+    let src = strip_prologue(src);
+    let open = find_tag(&src, "svg")?;
+    let tag_end = open + src[open..].find('>')?;
+    let (_, attrs, _) = parse_tag(&src[open..=tag_end])?;
+    let inner_end = src.rfind("</svg>").filter(|&e| e > tag_end)?;
+    let inner = &src[tag_end + 1..inner_end];
+
+    let attr = |k: &str| attrs.iter().find(|(n, _)| n.eq_ignore_ascii_case(k)).map(|(_, v)| v.as_str());
+    let num = |v: &str| v.trim().trim_end_matches("px").parse::<f64>().ok();
+    let vb: Option<[f64; 4]> = attr("viewBox").and_then(|v| {
+        let n: Vec<f64> = v.split([' ', ',']).filter(|s| !s.is_empty()).filter_map(|s| s.parse().ok()).collect();
+        (n.len() == 4).then(|| [n[0], n[1], n[2], n[3]])
+    });
+    let vb = vb
+        .or_else(|| Some([0.0, 0.0, num(attr("width")?)?, num(attr("height")?)?]))
+        .unwrap_or([0.0, 0.0, 24.0, 24.0]);
+
+    Some(Icon { view_box: square_view_box(vb), body: sanitize(inner, decimals(vb[2].max(vb[3]))) })
+}
+
+/// 縦横の短い方を広げて中央寄せの正方形にする（アイコンの大きさを揃えるため）
+fn square_view_box([x, y, w, h]: [f64; 4]) -> String {
+    let side = w.max(h);
+    let (x, y) = (x - (side - w) / 2.0, y - (side - h) / 2.0);
+    [x, y, side, side].iter().map(|v| fmt_num(*v)).collect::<Vec<_>>().join(" ")
+}
+
+fn fmt_num(v: f64) -> String {
+    let s = format!("{:.4}", v);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" { "0".to_string() } else { s.to_string() }
+}
+
+/// `<?xml ?>`・`<!DOCTYPE>`・コメントを取り除く
+fn strip_prologue(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut rest = src;
+    loop {
+        let next = [("<?", "?>"), ("<!--", "-->"), ("<!", ">")]
+            .iter()
+            .filter_map(|(open, close)| rest.find(open).map(|at| (at, *close)))
+            .min_by_key(|(at, _)| *at);
+        let Some((at, close)) = next else { break };
+        out.push_str(&rest[..at]);
+        // "<!--" と "<!" が同じ位置なら長い方（コメント）を優先する
+        let close = if rest[at..].starts_with("<!--") { "-->" } else { close };
+        match rest[at..].find(close) {
+            Some(end) => rest = &rest[at + end + close.len()..],
+            None => { rest = ""; break; }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn find_tag(src: &str, name: &str) -> Option<usize> {
+    let pat = format!("<{name}");
+    src.match_indices(&pat)
+        .map(|(at, _)| at)
+        .find(|&at| src[at + pat.len()..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/'))
+}
+
+type Attrs = Vec<(String, String)>;
+
+/// `<name a="b" ...>` / `</name>` / `<name/>` を (name, attrs, self_closing) にする。閉じタグは name が "/name"
+fn parse_tag(tag: &str) -> Option<(String, Attrs, bool)> {
+    let inner = tag.strip_prefix('<')?.strip_suffix('>')?;
+    let self_closing = inner.ends_with('/');
+    let inner = inner.trim_end_matches('/');
+    let name_end = inner.find(|c: char| c.is_whitespace()).unwrap_or(inner.len());
+    let name = inner[..name_end].to_string();
+    let mut attrs = Vec::new();
+    let s = &inner[name_end..];
+    let b = s.as_bytes();
+    let mut i = 0;
+    while i < b.len() {
+        while i < b.len() && b[i].is_ascii_whitespace() { i += 1; }
+        let start = i;
+        while i < b.len() && !b[i].is_ascii_whitespace() && b[i] != b'=' { i += 1; }
+        if start == i { i += 1; continue; }
+        let key = s[start..i].to_string();
+        while i < b.len() && b[i].is_ascii_whitespace() { i += 1; }
+        let mut val = String::new();
+        if i < b.len() && b[i] == b'=' {
+            i += 1;
+            while i < b.len() && b[i].is_ascii_whitespace() { i += 1; }
+            if i < b.len() && (b[i] == b'"' || b[i] == b'\'') {
+                let q = b[i];
+                let vs = i + 1;
+                i = vs;
+                while i < b.len() && b[i] != q { i += 1; }
+                val = s[vs..i.min(b.len())].to_string();
+                i += 1;
+            } else {
+                let vs = i;
+                while i < b.len() && !b[i].is_ascii_whitespace() { i += 1; }
+                val = s[vs..i].to_string();
+            }
+        }
+        attrs.push((key, val));
+    }
+    Some((name, attrs, self_closing))
+}
+
+/// スクリプト系の要素・on* 属性・外部/javascript: の参照を落とす。
+/// ついでにタグ間の空白を詰め、path の `d` を小数 `decimals` 桁に丸める。
+fn sanitize(src: &str, decimals: usize) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut rest = src;
+    let text = |out: &mut String, t: &str| {
+        if !t.trim().is_empty() {
+            out.push_str(t);
+        }
+    };
+    while let Some(lt) = rest.find('<') {
+        text(&mut out, &rest[..lt]);
+        let Some(gt) = rest[lt..].find('>') else { break };
+        let tag = &rest[lt..lt + gt + 1];
+        rest = &rest[lt + gt + 1..];
+        let Some((name, attrs, self_closing)) = parse_tag(tag) else { continue };
+        let lname = name.to_ascii_lowercase();
+        if let Some(closing) = lname.strip_prefix('/') {
+            if !DROP_ELEMENTS.contains(&closing) && !UNWRAP_ELEMENTS.contains(&closing) {
+                out.push_str(&format!("</{}>", &name[1..]));
+            }
+            continue;
+        }
+        if DROP_ELEMENTS.contains(&lname.as_str()) {
+            if !self_closing {
+                // 対応する閉じタグまで読み飛ばす
+                let close = format!("</{lname}");
+                match rest.to_ascii_lowercase().find(&close) {
+                    Some(at) => rest = rest[at..].find('>').map_or("", |e| &rest[at + e + 1..]),
+                    None => rest = "",
+                }
+            }
+            continue;
+        }
+        if UNWRAP_ELEMENTS.contains(&lname.as_str()) {
+            continue;
+        }
+        out.push('<');
+        out.push_str(&name);
+        for (k, v) in attrs {
+            let lk = k.to_ascii_lowercase();
+            // `javascript&#58;` や `java&#x09;script:` も見抜けるよう、文字参照を戻して空白・制御文字を除いてから調べる
+            let lv: String = decode_refs(&v).chars().filter(|c| !c.is_whitespace() && !c.is_control()).collect::<String>().to_ascii_lowercase();
+            let is_ref = lk == "href" || lk.ends_with(":href") || lk == "src";
+            if lk.starts_with("on") || (is_ref && !lv.starts_with('#')) || lv.contains("javascript:") {
+                continue;
+            }
+            let v = if lk == "d" { optimize_path(&v, decimals).unwrap_or(v) } else { v };
+            // 値は XML のまま（実体参照は生かす）。区切りを " に揃えるので " だけ逃がす
+            out.push_str(&format!(" {k}=\"{}\"", v.replace('"', "&quot;")));
+        }
+        out.push_str(if self_closing { "/>" } else { ">" });
+    }
+    text(&mut out, rest);
+    out
+}
+
+/// XML の文字参照（`&#58;` `&#x3a;` と定義済みの 5 つ）を戻す。知らないものはそのまま残す
+fn decode_refs(v: &str) -> String {
+    let mut out = String::with_capacity(v.len());
+    let mut rest = v;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let name = &rest[1..semi];
+            let c = match name {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let n = match name.strip_prefix(['#']) {
+                        Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok()?,
+                        Some(dec) => dec.parse().ok()?,
+                        None => return None,
+                    };
+                    char::from_u32(n)?
+                }
+            };
+            Some((c, semi + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 表示サイズに対して十分な小数桁数（viewBox の一辺の 1/1000 程度の精度）
+fn decimals(side: f64) -> usize {
+    if side <= 0.0 { return 3; }
+    (-(side / 1000.0).log10()).ceil().clamp(0.0, 4.0) as usize
+}
+
+/// パラメータの意味（相対座標の補正と丸め方に使う）
+#[derive(Clone, Copy, PartialEq)]
+enum Param { X, Y, Other, Flag }
+
+fn params_of(cmd: char) -> &'static [Param] {
+    use Param::*;
+    match cmd.to_ascii_uppercase() {
+        'M' | 'L' | 'T' => &[X, Y],
+        'H' => &[X],
+        'V' => &[Y],
+        'C' => &[X, Y, X, Y, X, Y],
+        'S' | 'Q' => &[X, Y, X, Y],
+        'A' => &[Other, Other, Other, Flag, Flag, X, Y],
+        _ => &[],
+    }
+}
+
+/// path の d を丸めて詰める。相対座標は丸め誤差が積もらないよう、丸めた後の現在位置から取り直す。
+/// 解釈できない d は None（元のまま使う）。
+fn optimize_path(d: &str, decimals: usize) -> Option<String> {
+    // 1) コマンドごとに分解（暗黙の繰り返しは別セグメントにする）
+    let b = d.as_bytes();
+    let mut segs: Vec<(char, Vec<f64>)> = Vec::new();
+    let mut i = 0;
+    let mut cmd = ' ';
+    let skip_sep = |i: &mut usize| {
+        while *i < b.len() && (b[*i].is_ascii_whitespace() || b[*i] == b',') { *i += 1; }
+    };
+    loop {
+        skip_sep(&mut i);
+        if i >= b.len() { break; }
+        if b[i].is_ascii_alphabetic() {
+            cmd = b[i] as char;
+            i += 1;
+            if !"MmLlHhVvCcSsQqTtAaZz".contains(cmd) { return None; }
+            if cmd.eq_ignore_ascii_case(&'z') {
+                segs.push((cmd, Vec::new()));
+                continue;
+            }
+        } else if cmd == ' ' || cmd.eq_ignore_ascii_case(&'z') {
+            return None;
+        } else if cmd == 'M' {
+            cmd = 'L';
+        } else if cmd == 'm' {
+            cmd = 'l';
+        }
+        let kinds = params_of(cmd);
+        let mut vals = Vec::with_capacity(kinds.len());
+        for &k in kinds {
+            skip_sep(&mut i);
+            if i >= b.len() { return None; }
+            if k == Param::Flag {
+                // フラグは区切り無しで続くことがある（"a1 1 0 011 1"）
+                match b[i] { b'0' => vals.push(0.0), b'1' => vals.push(1.0), _ => return None }
+                i += 1;
+                continue;
+            }
+            let start = i;
+            if b[i] == b'-' || b[i] == b'+' { i += 1; }
+            let mut dot = false;
+            while i < b.len() && (b[i].is_ascii_digit() || (b[i] == b'.' && !dot)) {
+                dot |= b[i] == b'.';
+                i += 1;
+            }
+            if i < b.len() && (b[i] == b'e' || b[i] == b'E') {
+                i += 1;
+                if i < b.len() && (b[i] == b'-' || b[i] == b'+') { i += 1; }
+                while i < b.len() && b[i].is_ascii_digit() { i += 1; }
+            }
+            vals.push(d[start..i].parse().ok()?);
+        }
+        segs.push((cmd, vals));
+    }
+
+    // 2) 丸めて書き出す
+    let round = |v: f64| {
+        let p = 10f64.powi(decimals as i32);
+        (v * p).round() / p
+    };
+    let mut out = String::new();
+    let (mut cur, mut rcur) = ((0.0, 0.0), (0.0, 0.0));
+    let (mut start, mut rstart) = ((0.0, 0.0), (0.0, 0.0));
+    let mut last_cmd = ' ';
+    for (cmd, vals) in segs {
+        let rel = cmd.is_ascii_lowercase();
+        // 同じコマンドの繰り返しと M の直後の L は文字を省略できる（M の繰り返しは L 扱いになるので省略しない）
+        let implicit = (cmd == last_cmd && !matches!(cmd, 'M' | 'm' | 'Z' | 'z'))
+            || (last_cmd == 'M' && cmd == 'L')
+            || (last_cmd == 'm' && cmd == 'l');
+        if !implicit {
+            out.push(cmd);
+        }
+        last_cmd = cmd;
+        let (mut end, mut rend) = (cur, rcur);
+        for (n, (&k, &v)) in params_of(cmd).iter().zip(&vals).enumerate() {
+            let emitted = match (k, rel) {
+                (Param::Flag, _) => v,
+                (Param::Other, _) => round(v),
+                (Param::X, true) => round(cur.0 + v - rcur.0),
+                (Param::Y, true) => round(cur.1 + v - rcur.1),
+                (_, false) => round(v),
+            };
+            match k {
+                Param::X => { end.0 = if rel { cur.0 + v } else { v }; rend.0 = if rel { rcur.0 + emitted } else { emitted }; }
+                Param::Y => { end.1 = if rel { cur.1 + v } else { v }; rend.1 = if rel { rcur.1 + emitted } else { emitted }; }
+                _ => {}
+            }
+            let s = fmt_coord(emitted);
+            // コマンド文字の直後・負号・（直前に小数点があるときの）"." 始まりは区切り不要
+            let joins = (n == 0 && !implicit) || s.starts_with('-') || (s.starts_with('.') && last_number_has_dot(&out));
+            let needs_sep = !joins;
+            if needs_sep {
+                out.push(' ');
+            }
+            out.push_str(&s);
+        }
+        match cmd.to_ascii_uppercase() {
+            'Z' => { cur = start; rcur = rstart; }
+            'M' => { cur = end; rcur = rend; start = end; rstart = rend; }
+            _ => { cur = end; rcur = rend; }
+        }
+    }
+    Some(out)
+}
+
+/// 0.5 → ".5"、-0.5 → "-.5"、1.0 → "1"
+fn fmt_coord(v: f64) -> String {
+    let s = fmt_num(v);
+    if let Some(r) = s.strip_prefix("0.") {
+        format!(".{r}")
+    } else if let Some(r) = s.strip_prefix("-0.") {
+        format!("-.{r}")
+    } else {
+        s
+    }
+}
+
+/// 直前の数値に小数点があるか（あれば次の ".5" は区切り無しでつなげられる）
+fn last_number_has_dot(out: &str) -> bool {
+    let tail = out.rsplit(|c: char| !(c.is_ascii_digit() || c == '.')).next().unwrap_or("");
+    tail.contains('.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_javascript_urls_are_dropped() {
+        let svg = r##"<g><a href="#x" xlink:href="javascript&#58;alert(1)"><path fill="url(java&#x09;script&#x3A;x)" d="M0 0h1"/></a><use href="#ok"/></g>"##;
+        assert_eq!(sanitize(svg, 2), r##"<g><path d="M0 0h1"/><use href="#ok"/></g>"##);
+    }
+
+    #[test]
+    fn animations_are_dropped() {
+        let svg = r#"<a><set attributeName="href" to="javascript&#58;alert(1)"/><animate attributeName="href" values="x"></animate><circle r="1"/></a>"#;
+        assert_eq!(sanitize(svg, 2), r#"<circle r="1"/>"#);
+    }
+
+    #[test]
+    fn builtin_icons_keep_the_default_fill_rule() {
+        let icons = Icons::load(Path::new("")).unwrap();
+        let github = &icons.map["github"];
+        assert!(github.body.starts_with(r#"<path fill="currentColor" d="M12"#), "{}", github.body);
+        assert!(icons.map.values().all(|i| !i.body.contains("fill-rule")));
+    }
+
+    #[test]
+    fn decode_refs_handles_numeric_and_named() {
+        assert_eq!(decode_refs("a&#58;b&#x3A;c&amp;&lt;&bogus;&"), "a:b:c&<&bogus;&");
+    }
+}
@@ -0,0 +1,317 @@
+/*
+ * FileName:        import
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 16:30:09
+ * LastModified:    2026-10-18 16:30:09
+ * Reference:       8ucchiman.github.io
+ * Description:     隣のリポジトリ（Cargo.toml / README.md）から Project を作る
+ */
+
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{BuildError, IoResultExt},
+    markdown, slugify, toml, Project,
+};
+
+/// 1 リポジトリから読み取った情報
+pub struct Imported {
+    pub dir: PathBuf,
+    /// Cargo の package.name（無ければディレクトリ名）。slug に使う。
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub repository: String,
+    pub keywords: Vec<String>,
+    pub image: String,
+}
+
+/// `projects_from` の各ディレクトリを読む。存在しないものは警告して飛ばす
+/// （CI では隣のチェックアウトが無いことが多いので）。
+pub fn load_all(dirs: &[PathBuf]) -> Result<Vec<Imported>, BuildError> {
+    let mut out = Vec::new();
+    for dir in dirs {
+        if !dir.is_dir() {
+            eprintln!("warning: projects_from: `{}` is not a directory, skipping", dir.display());
+            continue;
+        }
+        out.push(load(dir).map_err(|e| e.context(format!("importing `{}` (projects_from)", dir.display())))?);
+    }
+    Ok(out)
+}
+
+pub fn load(dir: &Path) -> Result<Imported, BuildError> {
+    let dir_name = dir
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| dir.to_string_lossy().into_owned());
+    let mut imp = Imported {
+        dir: dir.to_path_buf(),
+        name: dir_name,
+        title: String::new(),
+        description: String::new(),
+        repository: String::new(),
+        keywords: Vec::new(),
+        image: String::new(),
+    };
+
+    // Cargo.toml: [package] name / description / repository / keywords
+    let manifest = dir.join("Cargo.toml");
+    match fs::read_to_string(&manifest) {
+        Ok(src) => {
+            let doc = toml::parse(&src).map_err(|e| BuildError::toml(&manifest, &src, e))?;
+            if let Some(pkg) = doc.get("package").and_then(|v| v.as_table()) {
+                let get = |k: &str| pkg.get(k).and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                if !get("name").is_empty() { imp.name = get("name"); }
+                imp.description = get("description");
+                imp.repository = get("repository");
+                if imp.repository.is_empty() && get("homepage").contains("github.com") {
+                    imp.repository = get("homepage");
+                }
+                imp.keywords = pkg.get("keywords").map(|v| v.as_str_list()).unwrap_or_default();
+            }
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(BuildError::io(&manifest, e)),
+    }
+
+    // README.md: 最初の見出し・段落・画像
+    if let Some(readme) = find_readme(dir) {
+        let src = fs::read_to_string(&readme).at(&readme)?;
+        let info = readme_summary(&src);
+        imp.title = info.heading;
+        if imp.description.is_empty() { imp.description = info.paragraph; }
+        imp.image = match info.image {
+            Some(src) => resolve_image(&src, &imp.repository),
+            None => String::new(),
+        };
+    }
+    if imp.title.is_empty() { imp.title = imp.name.clone(); }
+    Ok(imp)
+}
+
+pub fn find_readme(dir: &Path) -> Option<PathBuf> {
+    ["README.md", "Readme.md", "readme.md", "README.markdown"]
+        .iter()
+        .map(|n| dir.join(n))
+        .find(|p| p.is_file())
+}
+
+#[derive(Default)]
+struct ReadmeSummary {
+    heading: String,
+    paragraph: String,
+    image: Option<String>,
+}
+
+/// バッジ画像（shields.io など）はサムネイルにしない
+fn is_badge(src: &str) -> bool {
+    ["shields.io", "badge.fury.io", "badgen.net", "/badge.svg", "/workflows/", "codecov.io", "docs.rs/", "crates.io/"]
+        .iter()
+        .any(|h| src.contains(h))
+}
+
+fn readme_summary(src: &str) -> ReadmeSummary {
+    let mut info = ReadmeSummary::default();
+    let html = markdown::to_html(src);
+
+    // 最初の画像（HTML の <img> も Markdown の画像も to_html 後は <img src="...">）
+    let mut rest = html.as_str();
+    while let Some(at) = rest.find("<img") {
+        rest = &rest[at + 4..];
+        if let Some(src) = attr_value(rest, "src")
+            && !is_badge(&src)
+        {
+            info.image = Some(src);
+            break;
+        }
+    }
+
+    // 最初の見出し
+    if let Some(line) = html.lines().map(str::trim).find(|t| t.starts_with("<h") && t.as_bytes().get(2).is_some_and(u8::is_ascii_digit)) {
+        info.heading = markdown::strip_tags(line).trim().to_string();
+    }
+    // 最初の「文章らしい」段落（バッジだけの段落などは飛ばす）
+    let mut rest = html.as_str();
+    while let Some(a) = rest.find("<p>") {
+        let Some(b) = rest[a..].find("</p>") else { break };
+        let text = markdown::strip_tags(&rest[a..a + b]);
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.chars().filter(|c| c.is_alphanumeric()).count() >= 12 {
+            info.paragraph = text;
+            break;
+        }
+        rest = &rest[a + b..];
+    }
+    info
+}
+
+/// `name="value"` の値（HTML エスケープは戻す）
+fn attr_value(tag: &str, name: &str) -> Option<String> {
+    let end = tag.find('>').unwrap_or(tag.len());
+    let tag = &tag[..end];
+    let at = tag.find(&format!("{name}="))? + name.len() + 1;
+    let rest = &tag[at..];
+    let v = match rest.chars().next()? {
+        q @ ('"' | '\'') => rest[1..].split(q).next()?,
+        _ => rest.split(|c: char| c.is_whitespace() || c == '/').next()?,
+    };
+    Some(v.replace("&quot;", "\"").replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&"))
+}
+
+/// "https://github.com/owner/repo(.git)" → ("owner", "repo")
+pub fn github_repo(url: &str) -> Option<(String, String)> {
+    let rest = url.trim().trim_end_matches('/').split("github.com/").nth(1)?;
+    let mut it = rest.split('/');
+    let owner = it.next().filter(|s| !s.is_empty())?;
+    let repo = it.next().filter(|s| !s.is_empty())?.trim_end_matches(".git");
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// README 内の相対パスの画像は GitHub の raw URL にする（リポジトリが分からなければ使わない）
+fn resolve_image(src: &str, repository: &str) -> String {
+    if src.contains("://") || src.starts_with("//") || src.starts_with("data:") {
+        return src.to_string();
+    }
+    match github_repo(repository) {
+        Some((owner, repo)) => format!(
+            "https://raw.githubusercontent.com/{owner}/{repo}/HEAD/{}",
+            src.trim_start_matches("./").trim_start_matches('/')
+        ),
+        None => String::new(),
+    }
+}
+
+pub fn to_project(imp: &Imported) -> Project {
+    Project {
+        title: imp.title.clone(),
+        image_url: imp.image.clone(),
+        github_url: imp.repository.clone(),
+        description: imp.description.clone(),
+        tags: imp.keywords.clone(),
+        slug: imp.name.clone(),
+        source: imp.dir.to_string_lossy().into_owned(),
+        ..Default::default()
+    }
+}
+
+/// 取り込んだ Project に設定側の Project を上書きする。
+/// slug か github_url が一致する設定があれば、その空でない項目が優先される。
+/// 一致しない設定の Project はそのまま残る。
+pub fn merge(config: Vec<Project>, imported: Vec<Project>) -> Vec<Project> {
+    let same_repo = |a: &str, b: &str| !a.is_empty() && github_repo(a).is_some() && github_repo(a) == github_repo(b);
+    let mut used = vec![false; config.len()];
+    let mut out = Vec::new();
+    for base in imported {
+        let hit = config
+            .iter()
+            .position(|c| slugify(&c.slug()) == slugify(&base.slug()) || same_repo(&c.github_url, &base.github_url));
+        match hit {
+            Some(i) => {
+                used[i] = true;
+                out.push(overlay(base, &config[i]));
+            }
+            None => out.push(base),
+        }
+    }
+    out.extend(config.into_iter().zip(used).filter(|(_, u)| !u).map(|(c, _)| c));
+    out
+}
+
+fn overlay(base: Project, over: &Project) -> Project {
+    fn pick(over: &str, base: String) -> String { if over.is_empty() { base } else { over.to_string() } }
+    fn pick_list(over: &[String], base: Vec<String>) -> Vec<String> { if over.is_empty() { base } else { over.to_vec() } }
+    Project {
+        title: pick(&over.title, base.title),
+        image_url: pick(&over.image_url, base.image_url),
+        github_url: pick(&over.github_url, base.github_url),
+        demo_url: pick(&over.demo_url, base.demo_url),
+        tags: pick_list(&over.tags, base.tags),
+        description: pick(&over.description, base.description),
+        tabs: pick_list(&over.tabs, base.tabs),
+        featured: over.featured || base.featured,
+        weight: if over.weight != 0 { over.weight } else { base.weight },
+        start: pick(&over.start, base.start),
+        end: pick(&over.end, base.end),
+        status: over.status.or(base.status),
+        slug: base.slug,
+        source: pick(&over.source, base.source),
+        role: pick(&over.role, base.role),
+        stack: pick_list(&over.stack, base.stack),
+        gallery: pick_list(&over.gallery, base.gallery),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(title: &str, slug: &str, github_url: &str) -> Project {
+        Project { title: title.into(), slug: slug.into(), github_url: github_url.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn config_fields_override_imported_ones() {
+        let mut imported = project("raytracer", "raytracer", "https://github.com/8ucchiman/raytracer");
+        imported.description = "From the README".into();
+        imported.tags = vec!["rust".into()];
+        imported.weight = 3;
+        let mut config = project("", "", "https://github.com/8ucchiman/raytracer.git/");
+        config.title = "Ray Tracer".into();
+        config.featured = true;
+        let other = project("Notes", "", "");
+
+        let out = merge(vec![config, other], vec![imported, project("cli", "cli", "")]);
+        assert_eq!(out.iter().map(|p| p.title.as_str()).collect::<Vec<_>>(), ["Ray Tracer", "cli", "Notes"]);
+        let p = &out[0];
+        // 設定で空の項目は取り込んだものが残る
+        assert_eq!((p.description.as_str(), p.tags.as_slice(), p.weight), ("From the README", &["rust".to_string()][..], 3));
+        assert!(p.featured);
+        assert_eq!(p.slug, "raytracer");
+        assert_eq!(p.github_url, "https://github.com/8ucchiman/raytracer.git/");
+
+        // slug でも対応づく
+        let mut config = project("CLI tool", "cli", "");
+        config.weight = -1;
+        let out = merge(vec![config], vec![project("cli", "cli", "")]);
+        assert_eq!(out.len(), 1);
+        assert_eq!((out[0].title.as_str(), out[0].weight), ("CLI tool", -1));
+    }
+
+    #[test]
+    fn readme_summary_skips_badges() {
+        let src = "\
+# Ray Tracer
+
+[![CI](https://github.com/8ucchiman/raytracer/workflows/ci/badge.svg)](#) ![crates](https://img.shields.io/crates/v/rt.svg)
+
+A small path tracer written in Rust, with BVH and importance sampling.
+
+![screenshot](docs/shot.png)
+";
+        let s = readme_summary(src);
+        assert_eq!(s.heading, "Ray Tracer");
+        assert_eq!(s.paragraph, "A small path tracer written in Rust, with BVH and importance sampling.");
+        assert_eq!(s.image.as_deref(), Some("docs/shot.png"));
+        assert_eq!(
+            resolve_image("./docs/shot.png", "https://github.com/8ucchiman/raytracer"),
+            "https://raw.githubusercontent.com/8ucchiman/raytracer/HEAD/docs/shot.png"
+        );
+        assert_eq!(resolve_image("docs/shot.png", ""), "");
+    }
+
+    #[test]
+    fn github_repo_strips_dot_git_and_slashes() {
+        let repo = |o: &str, r: &str| Some((o.to_string(), r.to_string()));
+        assert_eq!(github_repo("https://github.com/8ucchiman/raytracer.git"), repo("8ucchiman", "raytracer"));
+        assert_eq!(github_repo(" https://github.com/8ucchiman/raytracer/ "), repo("8ucchiman", "raytracer"));
+        assert_eq!(github_repo("https://github.com/8ucchiman/raytracer/tree/main"), repo("8ucchiman", "raytracer"));
+        assert_eq!(github_repo("https://github.com/8ucchiman"), None);
+        assert_eq!(github_repo("https://gitlab.com/8ucchiman/raytracer"), None);
+    }
+}
@@ -0,0 +1,250 @@
+/*
+ * FileName:        inflate
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 19:40:18
+ * LastModified:    2026-10-19 19:40:18
+ * Reference:       8ucchiman.github.io
+ * Description:     zlib（RFC 1950/1951）の展開。.git のオブジェクトを読むためだけのもの
+ */
+
+
+/// zlib ストリームを展開する。後ろに余計なバイトがあっても無視する（pack の中のエントリ用）。
+/// 壊れていれば None
+pub fn zlib(data: &[u8]) -> Option<Vec<u8>> {
+    let (&cmf, &flg) = (data.first()?, data.get(1)?);
+    // CM = 8（deflate）、ヘッダのチェック、プリセット辞書は無し
+    if cmf & 0x0f != 8 || (u16::from(cmf) << 8 | u16::from(flg)) % 31 != 0 || flg & 0x20 != 0 {
+        return None;
+    }
+    inflate(&data[2..])
+}
+
+/// 生の deflate ストリームを展開する
+pub fn inflate(data: &[u8]) -> Option<Vec<u8>> {
+    let mut r = Bits { data, pos: 0, bit: 0 };
+    let mut out = Vec::new();
+    loop {
+        let last = r.bits(1)? == 1;
+        match r.bits(2)? {
+            0 => {
+                // 非圧縮ブロック: バイト境界に揃えて LEN, NLEN
+                r.align();
+                let len = r.bits(16)? as usize;
+                let nlen = r.bits(16)? as usize;
+                if len != !nlen & 0xffff {
+                    return None;
+                }
+                out.extend_from_slice(r.data.get(r.pos..r.pos + len)?);
+                r.pos += len;
+            }
+            1 => {
+                let (lit, dist) = fixed_tables();
+                r.block(&mut out, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = r.dynamic_tables()?;
+                r.block(&mut out, &lit, &dist)?;
+            }
+            _ => return None,
+        }
+        if last {
+            return Some(out);
+        }
+    }
+}
+
+/// LSB から読むビットリーダ
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit: u32,
+}
+
+/// canonical Huffman 符号（長さごとの個数と、符号順に並べたシンボル）
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Option<Huffman> {
+        let mut counts = [0u16; 16];
+        for &l in lengths {
+            counts[usize::from(l)] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0u16; 16];
+        for i in 1..16 {
+            offsets[i] = offsets[i - 1] + counts[i - 1];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (sym, &l) in lengths.iter().enumerate() {
+            if l != 0 {
+                symbols[usize::from(offsets[usize::from(l)])] = sym as u16;
+                offsets[usize::from(l)] += 1;
+            }
+        }
+        Some(Huffman { counts, symbols })
+    }
+}
+
+const LEN_BASE: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LEN_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+/// 符号長の符号長が並ぶ順番
+const CL_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+fn fixed_tables() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+    for (i, l) in lengths.iter_mut().enumerate() {
+        *l = match i {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        };
+    }
+    (Huffman::new(&lengths).unwrap(), Huffman::new(&[5; 30]).unwrap())
+}
+
+impl Bits<'_> {
+    fn bits(&mut self, n: u32) -> Option<u32> {
+        let mut v = 0;
+        for i in 0..n {
+            let byte = *self.data.get(self.pos)?;
+            v |= u32::from(byte >> self.bit & 1) << i;
+            self.bit += 1;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.pos += 1;
+            }
+        }
+        Some(v)
+    }
+
+    fn align(&mut self) {
+        if self.bit != 0 {
+            self.bit = 0;
+            self.pos += 1;
+        }
+    }
+
+    /// 1 ビットずつ読んで符号長ごとの範囲に入るか見る（遅いが単純）
+    fn decode(&mut self, h: &Huffman) -> Option<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= self.bits(1)? as i32;
+            let count = i32::from(h.counts[len]);
+            if code - first < count {
+                return h.symbols.get((index + code - first) as usize).copied();
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        None
+    }
+
+    fn dynamic_tables(&mut self) -> Option<(Huffman, Huffman)> {
+        let hlit = self.bits(5)? as usize + 257;
+        let hdist = self.bits(5)? as usize + 1;
+        let hclen = self.bits(4)? as usize + 4;
+        let mut cl = [0u8; 19];
+        for &i in &CL_ORDER[..hclen] {
+            cl[i] = self.bits(3)? as u8;
+        }
+        let cl = Huffman::new(&cl)?;
+        let mut lengths = Vec::with_capacity(hlit + hdist);
+        while lengths.len() < hlit + hdist {
+            let (len, repeat) = match self.decode(&cl)? {
+                sym @ 0..=15 => (sym as u8, 1),
+                16 => (*lengths.last()?, 3 + self.bits(2)?),
+                17 => (0, 3 + self.bits(3)?),
+                18 => (0, 11 + self.bits(7)?),
+                _ => return None,
+            };
+            lengths.extend(std::iter::repeat_n(len, repeat as usize));
+        }
+        if lengths.len() != hlit + hdist {
+            return None;
+        }
+        Some((Huffman::new(&lengths[..hlit])?, Huffman::new(&lengths[hlit..])?))
+    }
+
+    fn block(&mut self, out: &mut Vec<u8>, lit: &Huffman, dist: &Huffman) -> Option<()> {
+        loop {
+            let sym = usize::from(self.decode(lit)?);
+            match sym {
+                0..=255 => out.push(sym as u8),
+                256 => return Some(()),
+                _ => {
+                    let i = sym - 257;
+                    let len = usize::from(*LEN_BASE.get(i)?) + self.bits(u32::from(LEN_EXTRA[i]))? as usize;
+                    let d = usize::from(self.decode(dist)?);
+                    let back = usize::from(*DIST_BASE.get(d)?) + self.bits(u32::from(DIST_EXTRA[d]))? as usize;
+                    if back > out.len() {
+                        return None;
+                    }
+                    // 重なりがありうるので 1 バイトずつ
+                    let start = out.len() - back;
+                    for k in 0..len {
+                        out.push(out[start + k]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+    }
+
+    // Python の zlib.compress で作ったもの。3 バイト目の下位ビットが最初のブロックの種類
+
+    #[test]
+    fn zlib_stored_block() {
+        let z = bytes("7801010c00f3ff68656c6c6f2c20776f726c641d540489");
+        assert_eq!(z[2] >> 1 & 3, 0);
+        assert_eq!(zlib(&z).unwrap(), b"hello, world");
+    }
+
+    #[test]
+    fn zlib_fixed_huffman_block() {
+        let z = bytes("789ccb48cdc9c9d75128cf2fca4901001d540489");
+        assert_eq!(z[2] >> 1 & 3, 1);
+        assert_eq!(zlib(&z).unwrap(), b"hello, world");
+        // 距離が長さより短いコピー（重なり）
+        let z = bytes("78da4b4cc4044948201902528020359104b500d6bb21ad");
+        assert_eq!(zlib(&z).unwrap(), b"aaaaaaaaaaaaaaaaaaaabbbbbbbbbbbbcccccccdddde".repeat(2));
+    }
+
+    #[test]
+    fn zlib_dynamic_huffman_block() {
+        let z = bytes(
+            "78da258cc111003008c2660de0fe2b14ad3e3c2002623286c126a246c75d322bea795d19a9e4811229a5a96c7063e9b3657a47ee532ebace6c09fb0a0fdffc2df4",
+        );
+        assert_eq!(z[2] >> 1 & 3, 2);
+        let want = b"aabaedecaaeaccadbaabaaaacacaadeabaadbacaababcabbecaeaccabbadaacbeaeabcaaaaaacbbdbacaacaebacebcbabdaaaaabaaaadababaabbaaa";
+        assert_eq!(zlib(&z).unwrap(), want);
+    }
+
+    #[test]
+    fn zlib_rejects_broken_streams() {
+        // 途中で切れたもの・非圧縮ブロックの LEN と NLEN が合わないもの・ヘッダのチェックが合わないもの
+        // （Adler-32 は見ない。pack の中では後ろに次のエントリが続くので）
+        let z = bytes("789ccb48cdc9c9d75128cf2fca4901001d540489");
+        assert_eq!(zlib(&z[..z.len() - 6]), None);
+        let mut stored = bytes("7801010c00f3ff68656c6c6f2c20776f726c641d540489");
+        stored[5] ^= 1;
+        assert_eq!(zlib(&stored), None);
+        assert_eq!(zlib(&[0x78, 0x9d, 0x03, 0x00]), None);
+    }
+}
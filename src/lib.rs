@@ -0,0 +1,877 @@
+/*
+ * FileName:        lib
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 17:05:42
+ * LastModified:    2026-10-19 17:05:42
+ * Reference:       8ucchiman.github.io
+ * Description:     サイト生成のライブラリ部分（モデル・ページ・埋め込みアセット）
+ */
+
+
+pub mod about;
+mod cache;
+pub mod contacts;
+mod context;
+mod error;
+mod git;
+pub mod icons;
+mod import;
+mod inflate;
+mod lint;
+mod markdown;
+mod pool;
+mod projects;
+mod qr;
+mod readme;
+mod related;
+mod remote;
+pub mod sections;
+mod sha1;
+mod site;
+mod tags;
+pub mod taxonomy;
+mod toml;
+mod vcard;
+
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    path::PathBuf,
+};
+
+use cache::Fingerprint;
+use icons::Icons;
+use sections::{Section, SectionKind};
+use taxonomy::Taxonomy;
+
+pub use cache::BuildReport;
+pub use context::BuildContext;
+pub use error::BuildError;
+pub use git::GitInfo;
+pub use pool::default_jobs;
+pub use qr::Ecc;
+pub use site::{diff_files, stale_files, write_files, Site, SiteBuilder};
+
+/// 生成物。dist 直下からの相対パス → 中身
+pub type Files = BTreeMap<PathBuf, Vec<u8>>;
+
+/// ヒーローのメディア（video 優先 → gif）の候補
+#[derive(Clone, Default)]
+pub struct Tab {
+    pub key: String,
+    pub label: String,
+    pub description: String,
+    pub gif_url: String,
+    pub video_url: String,
+}
+
+#[derive(Clone, Default)]
+pub struct Project {
+    pub title: String,
+    pub image_url: String,
+    pub github_url: String,
+    pub demo_url: String,
+    pub tags: Vec<String>,
+    pub description: String, // カードに出す 1〜2 行の説明
+    pub tabs: Vec<String>,   // 所属するタブの key（タグが tab key と一致する場合も所属扱い）
+    // ---- 並び順: featured → weight（小さい順）→ 日付（新しい順） ----
+    pub featured: bool,
+    pub weight: i32,
+    pub start: String, // "YYYY" / "YYYY-MM" / "YYYY-MM-DD"
+    pub end: String,   // 空なら継続中
+    pub status: Option<Status>,
+    // ---- 詳細ページ（dist/projects/<slug>/）用 ----
+    // 本文は content/projects/<slug>.md があればそれを使う
+    pub slug: String,   // 空ならタイトルから作る
+    pub source: String, // ローカルのチェックアウト（projects_from から取り込んだ場合）
+    pub role: String,
+    pub stack: Vec<String>,
+    pub gallery: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    Active,
+    Wip,
+    Archived,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Active => "active",
+            Status::Wip => "wip",
+            Status::Archived => "archived",
+        }
+    }
+}
+
+/// index_page() に渡す、描画の途中で Site から作ったもの
+pub struct IndexInputs<'a> {
+    /// リモート画像を差し替えたタブ
+    pub tabs: &'a [Tab],
+    /// 取り込み・タグの正規化・並べ替えを済ませた Project
+    pub projects: &'a [Project],
+    /// `projects` と同じ並びの関連 Project（添字, 関連度）
+    pub related: &'a [Vec<(usize, f64)>],
+    pub taxonomy: &'a Taxonomy,
+    pub icons: &'a Icons,
+    /// キャッシュバスター（`?v=`）
+    pub ver: &'a str,
+    /// About の写真を出力にコピーできたか（できなければ頭文字で代替）
+    pub has_portrait: bool,
+    /// フッターの著作権表示の年
+    pub year: i64,
+    /// ページ下部に出すビルド情報（空なら出さない）
+    pub build_info: &'a str,
+}
+
+/// トップページ。セクション・About・連絡先は `site` から、それ以外は `page` から取る
+pub fn index_page(site: &Site, page: &IndexInputs) -> String {
+    let IndexInputs { tabs, projects, related, taxonomy, icons, ver, has_portrait, year, build_info } = *page;
+    let (sections, about, contacts) = (&site.sections, &site.about, &site.contacts);
+    let (media_html, has_media) = pick_media_html(tabs);
+    let projects_json = projects_to_json(projects, related, taxonomy);
+    let chips = tags::tag_chips_html(&tags::collect_tags(projects, taxonomy), taxonomy, None, "");
+
+    // セクションを設定の順に並べる。ナビはヒーローの直後（ヒーローが無ければ先頭）
+    let nav = sections::nav_html(sections);
+    let mut body = Vec::new();
+    let enabled: Vec<&Section> = sections.iter().filter(|s| s.enabled).collect();
+    if !matches!(enabled.first().map(|s| &s.kind), Some(SectionKind::Hero)) {
+        body.push(format!("<!-- スクロール時に出現するタブ式ナビ -->\n{nav}"));
+    }
+    for s in enabled {
+        body.push(match s.kind {
+            SectionKind::Hero => hero_html(s, &media_html),
+            SectionKind::About => about::section_html(s, about, contacts, has_portrait),
+            SectionKind::Projects => projects_section_html(s, &chips),
+            SectionKind::Contact => contacts::section_html(s, contacts, icons, ver, site.contact_qr, year),
+            _ => sections::generic_html(s, projects),
+        });
+        if matches!(s.kind, SectionKind::Hero) && body.len() == 1 {
+            body.push(format!("<!-- スクロール時に出現するタブ式ナビ -->\n{nav}"));
+        }
+    }
+    let body = body.join("\n\n");
+
+    // r##" ... "## にして、HTML内の `"#` を安全に扱う
+    format!(r##"<!doctype html>
+<html lang="ja">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width,initial-scale=1">
+<title>8ucchiman | Portfolio</title>
+<link rel="preload" as="style" href="assets/style.css?v={v}">
+<link rel="stylesheet" href="assets/style.css?v={v}">
+<meta name="color-scheme" content="light dark">
+</head>
+<body>
+<div class="bg-orbs" aria-hidden="true"></div>
+
+{body}
+
+{fallback_note}{build_footer}
+<script>window.__PROJECTS__ = {projects_json};</script>
+<script src="assets/app.js?v={v}" defer></script>
+</body>
+</html>
+"##,
+        v = ver,
+        fallback_note = if has_media { String::new() } else { r#"<p class="desc">No media found. Put a GIF/MP4 under assets/ and set its path in the code.</p>"#.to_string() },
+        projects_json = projects_json,
+        build_footer = if build_info.is_empty() { String::new() } else { format!("\n<footer class=\"build-info\">{}</footer>", html_escape(build_info)) },
+    )
+}
+
+fn hero_html(s: &Section, media: &str) -> String {
+    format!(r##"<!-- フルスクリーン巨大プレビュー（ブラウザ全面） -->
+<section class="preview" id="{id}" aria-label="{label}">
+  <!-- 3行固定の見出し（常時表示） -->
+  <h2 class="headline">
+    <span>Where are you</span>
+    <span>going next,</span>
+    <span>8ucchiman?</span>
+  </h2>
+
+  <!-- 単一メディアのみ表示 -->
+  <div class="media" id="media">
+    {media}
+  </div>
+
+  <div class="fade"></div>
+</section>"##,
+        id = html_attr(&s.id),
+        label = html_attr(&s.label),
+    )
+}
+
+fn projects_section_html(s: &Section, chips: &str) -> String {
+    format!(r##"<section class="section" id="{id}" aria-label="{label}">
+  <div class="container">
+    <h3>{title}</h3>
+    <nav class="proj-controls" id="proj-controls" aria-label="project filters">{chips}</nav>
+    <div class="proj-grid" id="proj-grid" aria-live="polite"></div>
+  </div>
+</section>"##,
+        id = html_attr(&s.id),
+        label = html_attr(&s.label),
+        title = html_escape(&s.label),
+    )
+}
+
+/// tags/ や projects/ 以下の下層ページ用の共通レイアウト。
+/// `root` は dist 直下への相対パス（例: "../../"）。
+fn subpage(title: &str, root: &str, ver: &str, body: &str) -> String {
+    format!(r##"<!doctype html>
+<html lang="ja">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width,initial-scale=1">
+<title>{title} | 8ucchiman</title>
+<link rel="stylesheet" href="{root}assets/style.css?v={v}">
+<meta name="color-scheme" content="light dark">
+</head>
+<body>
+<div class="bg-orbs" aria-hidden="true"></div>
+<header class="subpage-head">
+  <a href="{root}">8ucchiman</a>
+  <a href="{root}#projects">projects</a>
+  <a href="{root}tags/">tags</a>
+</header>
+<main class="section subpage">
+  <div class="container">
+{body}
+  </div>
+</main>
+<script src="{root}assets/app.js?v={v}" defer></script>
+</body>
+</html>
+"##,
+        title = html_escape(title),
+        root = root,
+        v = ver,
+        body = body,
+    )
+}
+
+pub fn pick_media_html(tabs: &[Tab]) -> (String, bool) {
+    // 1) video_url 優先
+    if let Some(t) = tabs.iter().find(|t| !t.video_url.trim().is_empty()) {
+        let v = html_attr(&t.video_url);
+        return (format!(r#"<video playsinline muted loop autoplay preload="metadata" src="{v}"></video>"#), true);
+    }
+    // 2) なければ gif_url
+    if let Some(t) = tabs.iter().find(|t| !t.gif_url.trim().is_empty()) {
+        let g = html_attr(&t.gif_url);
+        return (format!(r#"<img loading="lazy" src="{g}" alt="preview gif">"#), true);
+    }
+    // 3) プレースホルダ
+    (r#"<div class="placeholder"></div>"#.to_string(), false)
+}
+
+// ---- helpers ----
+pub fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+pub fn html_attr(s: &str) -> String { html_escape(s).replace('\"', "&quot;") }
+
+/// URL・ファイル名に使う slug。小文字化し、英数字以外は `-` にまとめる。
+fn slugify(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    while out.ends_with('-') { out.pop(); }
+    if out.is_empty() { out.push_str("untitled"); }
+    out
+}
+
+/// href 用にパスをパーセントエンコードする（`/` と unreserved はそのまま）。
+fn url_path(s: &str) -> String {
+    let mut out = String::new();
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => out.push(b as char),
+            _ => out.push_str(&format!("%{b:02X}")),
+        }
+    }
+    out
+}
+
+/// url_path() の逆。`%XX` をバイトに戻す（不正なものはそのまま、UTF-8 でなければ置換文字）
+fn percent_decode(s: &str) -> String {
+    let b = s.as_bytes();
+    let mut out = Vec::with_capacity(b.len());
+    let mut i = 0;
+    while i < b.len() {
+        if b[i] == b'%'
+            && let Some(v) = s.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            out.push(v);
+            i += 3;
+            continue;
+        }
+        out.push(b[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// dist 直下基準の相対パス（`assets/...`）をサブページから参照できるよう `root` を付ける。
+/// スキームや `/` 始まりの URL はそのまま。
+fn asset_url(root: &str, url: &str) -> String {
+    let absolute = url.starts_with(['/', '#']) || url.split_once(':').is_some_and(|(s, _)| {
+        !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c))
+    });
+    if absolute || url.is_empty() { url.to_string() } else { format!("{root}{url}") }
+}
+
+/// "YYYY" / "YYYY-MM" / "YYYY-MM-DD" か
+fn valid_date(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    let num = |p: &str, len: usize, max: u32| p.len() == len && p.parse::<u32>().is_ok_and(|n| (1..=max).contains(&n) || len == 4);
+    match parts.as_slice() {
+        [y] => num(y, 4, 0),
+        [y, m] => num(y, 4, 0) && num(m, 2, 12),
+        [y, m, d] => num(y, 4, 0) && num(m, 2, 12) && num(d, 2, 31),
+        _ => false,
+    }
+}
+
+impl Project {
+    /// featured → weight（小さい順）→ 日付（新しい順）→ タイトル
+    fn display_order(a: &Project, b: &Project) -> Ordering {
+        b.featured
+            .cmp(&a.featured)
+            .then(a.weight.cmp(&b.weight))
+            .then_with(|| b.sort_date().cmp(a.sort_date()))
+            .then_with(|| a.title.cmp(&b.title))
+    }
+
+    /// 並び替え用の日付。継続中（end が空で archived でない）のものは一番新しい扱い。
+    fn sort_date(&self) -> &str {
+        if !self.end.is_empty() {
+            &self.end
+        } else if self.status != Some(Status::Archived) && !self.start.is_empty() {
+            "9999"
+        } else {
+            &self.start
+        }
+    }
+
+    /// "2023-04 – 2023-11" / "2024-02 – now"
+    fn timeframe(&self) -> String {
+        match (self.start.as_str(), self.end.as_str()) {
+            ("", "") => String::new(),
+            (s, "") if self.status == Some(Status::Archived) => s.to_string(),
+            (s, "") => format!("{s} – now"),
+            ("", e) => e.to_string(),
+            (s, e) if s == e => s.to_string(),
+            (s, e) => format!("{s} – {e}"),
+        }
+    }
+
+    /// 所属するタブの key。明示した `tabs` に加え、タグ（祖先込み）と一致する tab key も含む。
+    fn tab_keys<'t>(&self, tabs: &'t [Tab], tax: &Taxonomy) -> Vec<&'t str> {
+        let tags = tax.expand(&self.tags);
+        tabs.iter()
+            .filter(|t| self.tabs.contains(&t.key) || tags.contains(&taxonomy::normalize(&t.key).as_str()))
+            .map(|t| t.key.as_str())
+            .collect()
+    }
+
+    fn slug(&self) -> String {
+        if self.slug.is_empty() { slugify(&self.title) } else { slugify(&self.slug) }
+    }
+    /// dist 直下からの詳細ページの相対 URL
+    fn url(&self) -> String {
+        format!("projects/{}/", url_path(&self.slug()))
+    }
+}
+
+/// JS の card() と同じマークアップをサーバ側で生成する（タグページ用）
+/// `root` は dist 直下への相対パス。
+fn project_card_html(p: &Project, root: &str) -> String {
+    let tags: String = p.tags.iter().map(|t| format!(r#"<span class="tag">{}</span>"#, html_escape(t))).collect();
+    let gh = if p.github_url.is_empty() { String::new() } else {
+        format!(r#"<a class="btn small" href="{}" target="_blank" rel="noreferrer">GitHub</a>"#, html_attr(&p.github_url))
+    };
+    let dm = if p.demo_url.is_empty() { String::new() } else {
+        format!(r#"<a class="btn small ghost" href="{}" target="_blank" rel="noreferrer">Demo</a>"#, html_attr(&p.demo_url))
+    };
+    let img = if p.image_url.is_empty() { r#"<div style="height:180px"></div>"#.to_string() } else {
+        format!(r#"<img loading="lazy" src="{}" alt="{}">"#, html_attr(&asset_url(root, &p.image_url)), html_attr(&p.title))
+    };
+    let url = format!("{root}{}", p.url());
+    let desc = if p.description.is_empty() { String::new() } else {
+        format!(r#"<p class="proj-desc">{}</p>"#, html_escape(&p.description))
+    };
+    format!(
+        r#"<div class="proj-card{}"><a class="proj-link" href="{url}" tabindex="-1">{img}</a><div class="proj-badges">{}</div><div class="proj-info"><div><a class="proj-title" href="{url}">{}</a>{desc}<div class="proj-tags">{tags}</div></div><div class="proj-actions">{gh}{dm}</div></div></div>"#,
+        if p.featured { " featured" } else { "" },
+        project_badges_html(p),
+        html_escape(&p.title)
+    )
+}
+
+/// project_card_html() に出る項目を入力ハッシュに足す（カードを載せるページ用。カードに項目を足したらここにも足す）
+fn add_card_inputs(fp: &mut Fingerprint, p: &Project) {
+    fp.add(&p.title)
+        .add(p.slug())
+        .add(&p.image_url)
+        .add(&p.github_url)
+        .add(&p.demo_url)
+        .add(&p.description)
+        .add_list(&p.tags)
+        .add(if p.featured { "featured" } else { "" })
+        .add(p.status.map_or("", Status::as_str));
+}
+
+/// featured / status のバッジ（JS の badges() と同じ）
+fn project_badges_html(p: &Project) -> String {
+    let mut s = String::new();
+    if p.featured {
+        s.push_str(r#"<span class="badge featured">★ featured</span>"#);
+    }
+    if let Some(st) = p.status {
+        s.push_str(&format!(r#"<span class="badge status-{0}">{0}</span>"#, st.as_str()));
+    }
+    s
+}
+
+/// `all_tags` は祖先タグ込み（チップの絞り込み用）、`related` は関連 Project の URL（関連度順）
+pub fn projects_to_json(ps: &[Project], related: &[Vec<(usize, f64)>], taxonomy: &Taxonomy) -> String {
+    fn list<S: AsRef<str>>(tags: &[S]) -> String {
+        let mut t = String::from("[");
+        for (j, tg) in tags.iter().enumerate() {
+            if j > 0 { t.push(','); }
+            t.push_str(&json_str(tg.as_ref()));
+        }
+        t.push(']');
+        t
+    }
+    let mut s = String::from("[");
+    for (i, p) in ps.iter().enumerate() {
+        if i > 0 { s.push(','); }
+        let rel_urls: Vec<String> = related.get(i).into_iter().flatten().map(|&(j, _)| ps[j].url()).collect();
+        s.push_str(&format!(
+            "{{\"title\":{},\"url\":{},\"description\":{},\"image_url\":{},\"github_url\":{},\"demo_url\":{},\"tags\":{},\"all_tags\":{},\"status\":{},\"featured\":{},\"weight\":{},\"start\":{},\"end\":{},\"related\":{}}}",
+            json_str(&p.title), json_str(&p.url()), json_str(&p.description), json_str(&p.image_url), json_str(&p.github_url), json_str(&p.demo_url),
+            list(&p.tags), list(&taxonomy.expand(&p.tags)),
+            p.status.map(|s| format!("\"{}\"", s.as_str())).unwrap_or_else(|| "null".to_string()),
+            p.featured, p.weight, json_str(&p.start), json_str(&p.end),
+            list(&rel_urls)
+        ));
+    }
+    s.push(']');
+    s
+}
+
+/// JSON の文字列リテラル。値はそのまま（JSON として読めば元の文字列）で、HTML の <script> に埋め込んでも
+/// 抜けられないよう `<` `>` `&` と行区切り（U+2028/2029）も `\uXXXX` にする。innerHTML に入れるときは app.js が逃がす。
+fn json_str(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{0}'..='\u{1f}' | '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// ----------------- Embedded assets -----------------
+const STYLE_CSS: &str = r#"
+/* === mononoki を全体に適用（woff2 → ttf フォールバック） === */
+@font-face {
+  font-family: 'mononoki';
+  src: url('fonts/MononokiNerdFont-Regular.woff2') format('woff2'),
+  font-weight: 400;
+  font-style: normal;
+  font-display: swap;
+}
+@font-face {
+  font-family: 'mononoki';
+  src: url('fonts/MononokiNerdFont-Bold.woff2') format('woff2'),
+  font-weight: 700;
+  font-style: normal;
+  font-display: swap;
+}
+
+:root{
+  --bg1:#0b1220;--bg2:#0b1020;--fg:#e2e8f0;--muted:#94a3b8;--ring:rgba(255,255,255,.1);
+  /* 追加：モダンなグラデのアクセント色 */
+  --ac1:#60a5fa; /* sky-400 */
+  --ac2:#a78bfa; /* violet-400 */
+  --ac3:#34d399; /* emerald-400 */
+}
+@media (prefers-color-scheme: light){:root{--bg1:#f8fafc;--bg2:#eef2ff;--fg:#0f172a;--muted:#475569;--ring:rgba(0,0,0,.06)}}
+
+*{box-sizing:border-box}
+html,body{height:100%}
+html{scroll-behavior:smooth}
+body{
+  margin:0; font-family:'mononoki', monospace; font-size:16px; line-height:1.6; color:var(--fg);
+  background:linear-gradient(120deg,var(--bg1),var(--bg2)) fixed;
+}
+button,input,select,textarea{font:inherit}
+h1,h2,h3,h4,h5,h6,code,pre,.headline,.tablink{font-family:'mononoki', monospace}
+
+/* 背景のぼかしオーブ */
+.bg-orbs::before,.bg-orbs::after{content:"";position:fixed;inset:auto;filter:blur(60px);z-index:-1;border-radius:9999px}
+.bg-orbs::before{top:-60px;left:-40px;width:280px;height:280px;background:rgba(16,185,129,.18)}
+.bg-orbs::after{bottom:-80px;right:-60px;width:320px;height:320px;background:rgba(99,102,241,.16)}
+
+/* フルスクリーンの巨大プレビュー */
+.preview{
+  position:relative; width:100vw; height:100vh; overflow:hidden; background:#082b4b;
+}
+.media, .media img, .media video{
+  position:absolute; inset:0; width:100%; height:100%; object-fit:cover;
+}
+
+/* 3行見出し（縦中央寄せ・縦引き伸ばし・行間確保） */
+.headline{
+  position:absolute; left:1vw; top:0; bottom:0; z-index:3; pointer-events:none;
+  display:flex; flex-direction:column; justify-content:center;
+  font-weight:900; letter-spacing:-.02em; color:#fff; text-shadow:0 2px 14px rgba(0,0,0,.55);
+  margin:0; padding:0;
+}
+.headline span{
+  display:block; text-align:left;
+  font-size:clamp(28px, 12vh, 22vh);
+  line-height:1;
+  margin:4vh 0; padding:0;
+  transform:scaleY(1.5); transform-origin:left center; /* 縦に引き伸ばす */
+}
+
+/* 下部グラデで可読性UP */
+.fade{position:absolute;left:0;right:0;bottom:0;height:120px;background:linear-gradient(0deg,rgba(0,0,0,.45),transparent)}
+
+/* セクション */
+.section{
+  min-height:100vh; display:flex; align-items:center;
+  border-top:1px solid var(--ring);
+  background:linear-gradient(180deg, transparent, rgba(0,0,0,.04));
+}
+.container{max-width:1100px; margin:0 auto; padding:6vh 20px;}
+.section h3{margin:0 0 12px; font-size:clamp(24px, 5vw, 40px); font-weight:900;}
+.section p{margin:0; color:var(--muted)}
+.desc{max-width:1100px;margin:10px auto 32px;padding:0 20px;opacity:.8}
+
+/* === Projects grid === */
+.proj-controls{ display:flex; gap:8px; flex-wrap:wrap; margin:10px 0 16px; }
+.chip{ appearance:none; border:1px solid var(--ring); background:transparent; color:inherit; padding:6px 10px; border-radius:999px; cursor:pointer; font-weight:700; text-decoration:none; display:inline-block; }
+.chip.sub{ font-weight:400; }
+.chip.sub::before{ content:"↳ "; opacity:.6; }
+.chip-count{ margin-left:6px; font-weight:400; opacity:.7; }
+.chip.active{ background:linear-gradient(135deg, var(--ac1), var(--ac2)); color:#fff; border-color:transparent; }
+.proj-grid{ display:grid; gap:14px; grid-template-columns:repeat(auto-fill, minmax(240px, 1fr)); }
+.proj-card{ position:relative; overflow:hidden; border-radius:16px; background:rgba(255,255,255,.03); border:1px solid var(--ring); box-shadow:0 10px 22px var(--ring); }
+.proj-card img{ width:100%; height:180px; object-fit:cover; display:block; filter:saturate(1.02); transition: transform .35s ease; }
+.proj-card:hover img{ transform: scale(1.05); }
+.proj-info{ padding:10px 12px; display:flex; align-items:center; justify-content:space-between; gap:8px; }
+.proj-title{ font-weight:900; letter-spacing:-.01em; color:inherit; text-decoration:none; }
+a.proj-title:hover{ text-decoration:underline; }
+.proj-link{ display:block; }
+.proj-desc{ margin:2px 0 6px; font-size:.9rem; opacity:.85; }
+.proj-card.featured{ border-color:transparent; box-shadow:0 0 0 2px var(--ac1), 0 10px 22px var(--ring); }
+.proj-badges{ position:absolute; top:10px; left:10px; display:flex; gap:6px; }
+.badge{ font-size:11px; font-weight:800; letter-spacing:.04em; text-transform:uppercase; padding:2px 8px; border-radius:999px; color:#fff; background:rgba(15,23,42,.7); }
+.badge.featured{ background:linear-gradient(135deg, var(--ac1), var(--ac2)); }
+.badge.status-active{ background:#059669; }
+.badge.status-wip{ background:#d97706; }
+.badge.status-archived{ background:#475569; }
+.proj-tags{ display:flex; gap:6px; flex-wrap:wrap; opacity:.8; }
+.tag{ font-size:12px; border:1px solid var(--ring); border-radius:999px; padding:2px 6px; }
+.proj-actions{ display:flex; gap:8px; }
+.btn.small{ padding:6px 10px; border-radius:10px; font-weight:800; }
+
+/* === サブページ（tags/ など） === */
+.subpage{ min-height:100vh; }
+.subpage-head{ display:flex; gap:12px; align-items:center; flex-wrap:wrap; padding:16px 20px; border-bottom:1px solid var(--ring); }
+.subpage-head a{ color:inherit; text-decoration:none; font-weight:800; }
+.subpage-head a:hover{ text-decoration:underline; }
+.tag-index{ list-style:none; margin:0; padding:0; display:flex; flex-direction:column; gap:8px; }
+.tag-index li{ display:flex; gap:12px; align-items:baseline; }
+.tag-index .depth-1{ margin-left:24px; } .tag-index .depth-2{ margin-left:48px; } .tag-index .depth-3{ margin-left:72px; }
+.tag-desc{ font-size:.9rem; }
+
+/* === Project 詳細ページ === */
+.proj-hero{ width:100%; max-height:420px; object-fit:cover; border-radius:16px; border:1px solid var(--ring); display:block; margin:8px 0 16px; }
+.proj-meta{ display:grid; grid-template-columns:max-content 1fr; gap:4px 16px; margin:0 0 16px; }
+.proj-meta dt{ font-weight:800; opacity:.7; }
+.proj-meta dd{ margin:0; }
+.stack{ list-style:none; margin:0; padding:0; display:flex; gap:6px; flex-wrap:wrap; }
+.proj-body{ margin:24px 0; line-height:1.8; }
+.proj-body p{ margin:0 0 1em; color:inherit; }
+.proj-body img{ max-width:100%; }
+.proj-body pre{ overflow:auto; padding:12px; border-radius:10px; border:1px solid var(--ring); background:rgba(0,0,0,.2); }
+.proj-body table{ border-collapse:collapse; margin:0 0 1em; display:block; overflow:auto; }
+.proj-body th, .proj-body td{ border:1px solid var(--ring); padding:6px 12px; }
+.proj-body .task-list-item{ list-style:none; }
+.proj-body .task-list-item input{ margin:0 .4em 0 -1.4em; }
+.markdown-alert{ margin:0 0 1em; padding:8px 16px; border-left:4px solid var(--muted); border-radius:4px; background:rgba(255,255,255,.03); }
+.markdown-alert-title{ font-weight:800; margin-bottom:4px !important; }
+.markdown-alert-note{ border-color:#60a5fa; } .markdown-alert-tip{ border-color:#34d399; }
+.markdown-alert-important{ border-color:#a78bfa; } .markdown-alert-warning{ border-color:#f59e0b; } .markdown-alert-caution{ border-color:#ef4444; }
+.proj-lead{ font-size:1.05rem; margin:0 0 12px; }
+.proj-badges-inline{ display:flex; gap:6px; }
+.gallery{ display:grid; gap:10px; grid-template-columns:repeat(auto-fill, minmax(200px, 1fr)); margin:16px 0; }
+.gallery img{ width:100%; height:160px; object-fit:cover; border-radius:12px; border:1px solid var(--ring); display:block; }
+.proj-related{ margin-top:32px; }
+.proj-related h4{ margin:0 0 12px; font-size:1.1rem; font-weight:900; }
+.proj-pager{ display:flex; justify-content:space-between; gap:12px; margin-top:32px; padding-top:16px; border-top:1px solid var(--ring); }
+.proj-pager a{ color:inherit; text-decoration:none; font-weight:800; }
+.proj-pager a:hover{ text-decoration:underline; }
+
+/* === Contact links === */
+.contact-links{ display:flex; gap:10px; flex-wrap:wrap; margin-top:10px; justify-content:center; }
+.icon-link{ display:inline-flex; align-items:center; justify-content:center; gap:0; padding:10px; width:42px; height:42px; border-radius:12px; border:1px solid var(--ring); background:rgba(255,255,255,.03); color:inherit; text-decoration:none; box-shadow:0 10px 22px var(--ring); }
+.icon-link:hover{ background:linear-gradient(135deg, var(--ac1), var(--ac2)); color:#fff; border-color:transparent; }
+.icon{ width:20px; height:20px; display:block; }
+
+/* Compact contact section */
+#contact{ min-height:auto; }
+#contact .container{ padding:22px 20px; }
+#contact h3{ font-size:clamp(18px,2.8vw,24px); margin-bottom:8px; }
+.contact-links{ gap:12px; }
+.icon-link{ padding:10px; width:80px; height:80px; border-radius:12px; box-shadow:0 8px 18px var(--ring); }
+.icon{ width:70px; height:77px; }
+/* small text under icons */
+.contact-column{ display:flex; flex-direction:column; align-items:center; gap:48px; margin-top:8px; }
+.contact-meta{ margin-top:0; text-align:center; display:flex; flex-direction:column; align-items:center; gap:40px; }
+.contact-line{ margin:0; font-size:.8rem; font-weight:400; opacity:.9; }
+.contact-credit{ margin:0; font-size:.9rem; opacity:.75; }
+.build-info{ margin:0 0 24px; text-align:center; font-size:.75rem; opacity:.5; font-family:ui-monospace, monospace; }
+.mail-link{ color:inherit; text-decoration:none; border-bottom:1px dotted currentColor; font-size:1rem; font-weight:600; }
+.mail-link:hover{ text-decoration:underline; }
+.contact-qr{ margin:0; display:flex; flex-direction:column; align-items:center; gap:8px; font-size:.85rem; }
+.contact-qr img{ width:160px; height:160px; border-radius:8px; background:#fff; }
+
+/* スクロール時に出現するタブ（A: スライドダウン＋フェード） */
+.sticky-tabs{
+  position:fixed; top:12px; left:50%; transform:translate(-50%, -12px);
+  display:flex; gap:8px;
+  background:rgba(15,23,42,.55);
+  -webkit-backdrop-filter: blur(8px); backdrop-filter: blur(8px);
+  border:1px solid var(--ring); padding:8px; border-radius:999px; z-index:10;
+
+  opacity:0; pointer-events:none;
+  transition:transform .35s cubic-bezier(.22,.61,.36,1), opacity .25s ease;
+}
+.sticky-tabs.visible{ transform:translate(-50%, 0); opacity:1; pointer-events:auto; }
+
+.tablink{
+  appearance:none; border:0; border-radius:999px; padding:8px 14px;
+  color:#fff; background:transparent; font-weight:800; cursor:pointer;
+}
+.tablink:hover{background:rgba(255,255,255,.08)}
+.tablink:focus{outline:2px solid rgba(255,255,255,.35); outline-offset:2px}
+
+.tablink.active{
+  background: linear-gradient(135deg, var(--ac1), var(--ac2), var(--ac3));
+  color:#fff;
+  box-shadow:
+    0 6px 18px rgba(0,0,0,.28),
+    0 0 0 1px rgba(255,255,255,.18) inset;
+  transform: translateY(-1px);
+}
+
+/* 低モーション設定への配慮 */
+@media (prefers-reduced-motion: reduce){
+  .sticky-tabs, .sticky-tabs.visible, .tablink{ transition:none !important; transform:none !important; filter:none !important; }
+}
+
+/* プレースホルダ */
+.placeholder{ width:100%; height:100%; background:linear-gradient(135deg, rgba(148,163,184,.25), rgba(226,232,240,.35)); }
+
+/* === About me layout === */
+.about-grid{ display:grid; grid-template-columns:1fr; gap:18px; align-items:center; }
+@media(min-width:900px){ .about-grid{ grid-template-columns: 1.1fr 1.3fr; } }
+.about-media{ width:100%; }
+.about-frame{ position:relative; border-radius:20px; overflow:hidden; background:linear-gradient(135deg,#111827,#0b0f1a); box-shadow:0 16px 40px var(--ring); aspect-ratio:4/3; }
+.about-frame img{ position:absolute; inset:0; width:100%; height:100%; object-fit:cover; filter:saturate(1.02); }
+.about-text{ padding:8px 6px; }
+.about-kicker{ margin:0 0 6px; font-size:12px; letter-spacing:.12em; text-transform:uppercase; opacity:.7; }
+.about-title{ margin:.1rem 0 10px; font-size:clamp(24px,3vw,36px); font-weight:900; letter-spacing:-.02em; }
+.about-card{ border:1px solid var(--ring); border-radius:14px; padding:14px 16px; background:rgba(255,255,255,.03); box-shadow:0 6px 16px var(--ring); }
+@media (prefers-color-scheme: light){ .about-card{ background:rgba(15,23,42,.03); } }
+.about-name{ margin:.2rem 0 .2rem; font-size:clamp(18px,2.2vw,24px); font-weight:800; }
+.about-role{ margin:0 0 .6rem; opacity:.8; font-size:.95rem; }
+.about-bio{ margin:.2rem 0 1rem; line-height:1.7; opacity:.95; }
+.about-bio p + p{ margin-top:.6rem; }
+.about-fallback{ position:absolute; inset:0; display:grid; place-items:center; font-family:'mononoki', monospace; font-size:clamp(64px,12vw,140px); font-weight:900; opacity:.35; }
+
+/* posts / publications / timeline セクション */
+.entry-list, .timeline{ list-style:none; margin:0; padding:0; display:flex; flex-direction:column; gap:10px; }
+.timeline{ border-left:2px solid var(--ring); padding-left:16px; }
+.entry-date{ display:inline-block; min-width:9em; font-family:'mononoki', monospace; font-size:.9rem; opacity:.7; }
+.entry-note{ opacity:.7; font-size:.9rem; }
+"#;
+
+const APP_JS: &str = r#"
+// スクロール量に応じてタブ表示切替＋アクティブハイライト＋クリックでスムーススクロール
+(function(){
+  const tabs = document.getElementById('stickyTabs');
+  const home = document.querySelector('.preview');
+  if (!tabs) return;
+
+  // --- 1) タブの表示/非表示（ヒーロー領域から離れたら表示。ヒーローが無ければ常に表示） ---
+  if (!home) {
+    tabs.classList.add('visible');
+  } else if ('IntersectionObserver' in window) {
+    const io = new IntersectionObserver(([entry])=>{
+      const mostlyVisible = entry.intersectionRatio > 0.6;
+      tabs.classList.toggle('visible', !mostlyVisible);
+    }, { threshold: [0, 0.6, 1] });
+    io.observe(home);
+  } else {
+    const onScrollShowHide = ()=>{
+      const y = window.scrollY || document.documentElement.scrollTop;
+      tabs.classList.toggle('visible', y > window.innerHeight * 0.4);
+    };
+    window.addEventListener('scroll', onScrollShowHide, { passive:true });
+    onScrollShowHide();
+  }
+
+  // --- 2) セクションに応じてアクティブなタブをハイライト ---
+  const links = Array.from(tabs.querySelectorAll('.tablink'));
+  const sections = links
+    .map(btn => document.querySelector(btn.getAttribute('data-target')))
+    .filter(Boolean);
+
+  function setActive(btn){
+    links.forEach(b => {
+      const active = b === btn;
+      b.classList.toggle('active', active);
+      if (active) b.setAttribute('aria-current','page');
+      else b.removeAttribute('aria-current');
+    });
+  }
+
+  // 画面中央に最も近いセクションのタブをアクティブにする
+  let ticking = false;
+  function updateActive(){
+    if (sections.length === 0) return;
+    const mid = window.innerHeight / 2;
+    let bestIdx = 0;
+    let bestDist = Infinity;
+    for (let i=0; i<sections.length; i++){
+      const r = sections[i].getBoundingClientRect();
+      const center = r.top + r.height/2;
+      const dist = Math.abs(center - mid);
+      if (dist < bestDist){ bestDist = dist; bestIdx = i; }
+    }
+    setActive(links[bestIdx]);
+  }
+
+  window.addEventListener('scroll', ()=>{
+    if (ticking) return;
+    ticking = true;
+    requestAnimationFrame(()=>{ updateActive(); ticking = false; });
+  }, { passive:true });
+
+  // --- 3) クリックで対象セクションへスクロール＆即ハイライト ---
+  links.forEach(btn=>{
+    btn.addEventListener('click', ()=>{
+      const target = btn.getAttribute('data-target');
+      const el = document.querySelector(target);
+      if (!el) return;
+      try { el.scrollIntoView({ behavior:'smooth', block:'start' }); }
+      catch { location.hash = target; }
+      setActive(btn);
+      // スムーススクロール後の最終位置で再評価
+      setTimeout(updateActive, 700);
+    });
+  });
+
+  // 初期状態
+  updateActive();
+})();
+
+// 難読化した連絡先（data-contact）を復元して href を付ける
+(function(){
+  const decode = {
+    rev: s => Array.from(s).reverse().join(''),
+    rot13: s => s.replace(/[a-z]/gi, c => {
+      const base = c <= 'Z' ? 65 : 97;
+      return String.fromCharCode((c.charCodeAt(0) - base + 13) % 26 + base);
+    }),
+  };
+  document.querySelectorAll('[data-contact]').forEach(a => {
+    const f = decode[a.getAttribute('data-contact-enc')];
+    if (!f) return;
+    const url = f(a.getAttribute('data-contact'));
+    a.setAttribute('href', url);
+    if (a.hasAttribute('data-contact-text')) a.textContent = url.replace(/^mailto:/, '');
+  });
+})();
+
+// Projects: filter chips + grid render
+// チップはサーバ側で tags/<tag>/ へのリンクとして出力済み。JS が有効ならその場で絞り込む。
+(function(){
+  const data = (window.__PROJECTS__ || []);
+  const grid = document.getElementById('proj-grid');
+  const controls = document.getElementById('proj-controls');
+  if (!grid || !controls || !Array.isArray(data) || data.length === 0) return;
+
+  let active = 'all';
+  const chips = Array.from(controls.querySelectorAll('.chip'));
+
+  function renderChips(){
+    chips.forEach(a => {
+      const on = a.getAttribute('data-tag') === active;
+      a.classList.toggle('active', on);
+      if (on) a.setAttribute('aria-current','page');
+      else a.removeAttribute('aria-current');
+    });
+  }
+
+  chips.forEach(a=>{
+    a.addEventListener('click', (ev)=>{
+      if (ev.metaKey || ev.ctrlKey || ev.shiftKey || ev.button !== 0) return; // 新しいタブで開く操作はリンクに任せる
+      ev.preventDefault();
+      active = a.getAttribute('data-tag');
+      render();
+    });
+  });
+
+  // JSON の値は生の文字列なので、innerHTML に入れる前にここで逃がす
+  const esc = v => String(v ?? '').replace(/[&<>"']/g, c => ({'&':'&amp;','<':'&lt;','>':'&gt;','"':'&quot;',"'":'&#39;'})[c]);
+
+  function card(p){
+    const tags = (p.tags||[]).map(t=>`<span class=\"tag\">${esc(t)}</span>`).join('');
+    const gh = p.github_url ? `<a class=\"btn small\" href=\"${esc(p.github_url)}\" target=\"_blank\" rel=\"noreferrer\">GitHub</a>` : '';
+    const dm = p.demo_url ? `<a class=\"btn small ghost\" href=\"${esc(p.demo_url)}\" target=\"_blank\" rel=\"noreferrer\">Demo</a>` : '';
+    const img = p.image_url ? `<img loading=\"lazy\" src=\"${esc(p.image_url)}\" alt=\"${esc(p.title)}\">` : '<div style="height:180px"></div>';
+    const desc = p.description ? `<p class=\"proj-desc\">${esc(p.description)}</p>` : '';
+    return `<div class=\"proj-card${p.featured?' featured':''}\"><a class=\"proj-link\" href=\"${esc(p.url)}\" tabindex=\"-1\">${img}</a><div class=\"proj-badges\">${badges(p)}</div><div class=\"proj-info\"><div><a class=\"proj-title\" href=\"${esc(p.url)}\">${esc(p.title)}</a>${desc}<div class=\"proj-tags\">${tags}</div></div><div class=\"proj-actions\">${gh}${dm}</div></div></div>`;
+  }
+
+  function badges(p){
+    const f = p.featured ? '<span class=\"badge featured\">★ featured</span>' : '';
+    const s = p.status ? `<span class=\"badge status-${esc(p.status)}\">${esc(p.status)}</span>` : '';
+    return f + s;
+  }
+
+  function render(){
+    renderChips();
+    const list = active==='all' ? data : data.filter(p => (p.all_tags||p.tags||[]).includes(active));
+    grid.innerHTML = list.map(card).join('');
+  }
+
+  render();
+})();
+"#;
@@ -0,0 +1,195 @@
+/*
+ * FileName:        lint
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 19:04:31
+ * LastModified:    2026-10-18 19:04:31
+ * Reference:       8ucchiman.github.io
+ * Description:     コンテンツのリンタ（プレースホルダ・重複・空のタブなど）
+ */
+
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{taxonomy::Taxonomy, valid_date, Project, Tab};
+
+/// サンプルのまま残りがちなホスト・パス
+const PLACEHOLDER_URLS: &[&str] = &[
+    "github.com/you/",
+    "github.com/username/",
+    "github.com/your-name/",
+    "example.com",
+    "example.org",
+    "example.net",
+    "localhost",
+    "127.0.0.1",
+];
+
+/// ダミー画像サービス
+const PLACEHOLDER_IMAGE_HOSTS: &[&str] = &[
+    "picsum.photos",
+    "placehold.co",
+    "placehold.it",
+    "placeholder.com",
+    "dummyimage.com",
+    "placekitten.com",
+    "loremflickr.com",
+    "source.unsplash.com",
+    "fakeimg.pl",
+];
+
+pub struct Warning {
+    /// ルール名（`warning[placeholder-url]` のように表示する）
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "warning[{}]: {}", self.code, self.message)
+    }
+}
+
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map(|x| x.1).unwrap_or(url);
+    rest.split(['/', '?', '#']).next().unwrap_or("")
+}
+
+fn is_placeholder_url(url: &str) -> bool {
+    let u = url.to_ascii_lowercase();
+    let h = host(&u);
+    PLACEHOLDER_URLS.iter().any(|p| if p.contains('/') { u.contains(p) } else { h == *p || h.ends_with(&format!(".{p}")) })
+}
+
+fn is_placeholder_image(url: &str) -> bool {
+    let u = url.to_ascii_lowercase();
+    let h = host(&u);
+    PLACEHOLDER_IMAGE_HOSTS.iter().any(|p| h == *p || h.ends_with(&format!(".{p}")) || (p.contains('/') && u.contains(p)))
+}
+
+/// タブと Project を検査して警告を返す。
+pub fn check(tabs: &[Tab], projects: &[Project], tax: &Taxonomy) -> Vec<Warning> {
+    let mut w = Vec::new();
+    let mut warn = |code, message: String| w.push(Warning { code, message });
+
+    // ---- タブ ----
+    let mut keys: BTreeMap<&str, usize> = BTreeMap::new();
+    for t in tabs {
+        *keys.entry(t.key.as_str()).or_default() += 1;
+    }
+    for (k, n) in &keys {
+        if *n > 1 {
+            warn("duplicate-tab-key", format!("tab key `{k}` is used by {n} tabs"));
+        }
+    }
+    for t in tabs {
+        if t.gif_url.trim().is_empty() && t.video_url.trim().is_empty() {
+            warn("empty-media", format!("tab `{}` has neither gif_url nor video_url", t.key));
+        }
+        for url in [&t.gif_url, &t.video_url] {
+            if is_placeholder_image(url) {
+                warn("placeholder-image", format!("tab `{}`: media `{url}` is hosted on a placeholder service", t.key));
+            }
+        }
+        if !projects.iter().any(|p| p.tab_keys(tabs, tax).contains(&t.key.as_str())) {
+            warn("empty-tab", format!("tab `{}` has no matching projects", t.key));
+        }
+    }
+    duplicates(tabs.iter().map(|t| (t.key.as_str(), t.description.as_str())), "tabs", &mut warn);
+
+    // ---- Project ----
+    for p in projects {
+        let name = &p.title;
+        for (field, url) in [("github_url", &p.github_url), ("demo_url", &p.demo_url), ("image_url", &p.image_url)] {
+            if is_placeholder_url(url) {
+                warn("placeholder-url", format!("project `{name}`: {field} `{url}` looks like a placeholder"));
+            }
+        }
+        for url in std::iter::once(&p.image_url).chain(&p.gallery) {
+            if is_placeholder_image(url) {
+                warn("placeholder-image", format!("project `{name}`: image `{url}` is hosted on a placeholder service"));
+            }
+        }
+        if p.image_url.trim().is_empty() {
+            warn("empty-media", format!("project `{name}` has no image_url"));
+        }
+        for (field, d) in [("start", &p.start), ("end", &p.end)] {
+            if !d.is_empty() && !valid_date(d) {
+                warn("malformed-date", format!("project `{name}`: {field} `{d}` is not YYYY, YYYY-MM or YYYY-MM-DD"));
+            }
+        }
+        for t in &p.tabs {
+            if !keys.contains_key(t.as_str()) {
+                warn("unknown-tab", format!("project `{name}` refers to unknown tab `{t}`"));
+            }
+        }
+    }
+    duplicates(projects.iter().map(|p| (p.title.as_str(), p.description.as_str())), "projects", &mut warn);
+    w
+}
+
+/// 同じ説明文を使い回しているものを報告する
+fn duplicates<'a>(items: impl Iterator<Item = (&'a str, &'a str)>, kind: &str, warn: &mut impl FnMut(&'static str, String)) {
+    let mut by_desc: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for (name, desc) in items {
+        let d = desc.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        if !d.is_empty() {
+            by_desc.entry(d).or_default().push(name);
+        }
+    }
+    for (desc, names) in by_desc {
+        if names.len() > 1 {
+            let names: Vec<String> = names.iter().map(|n| format!("`{n}`")).collect();
+            warn("duplicate-description", format!("{kind} {} share the description \"{desc}\"", names.join(", ")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(key: &str, description: &str, gif_url: &str) -> Tab {
+        Tab { key: key.into(), label: key.into(), description: description.into(), gif_url: gif_url.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn sample_content_is_reported() {
+        let tabs = [
+            tab("3d", "Rendering experiments", "https://picsum.photos/seed/3d/800/600"),
+            tab("3d", "rendering   EXPERIMENTS", "assets/3d.gif"),
+            tab("audio", "Sound", "assets/audio.gif"),
+        ];
+        let projects = [Project {
+            title: "Alpha".into(),
+            github_url: "https://github.com/you/alpha".into(),
+            image_url: "https://picsum.photos/seed/alpha/800/600".into(),
+            tabs: vec!["3d".into()],
+            ..Default::default()
+        }];
+        let w = check(&tabs, &projects, &Taxonomy::default());
+        let codes: Vec<&str> = w.iter().map(|w| w.code).collect();
+        assert_eq!(
+            codes,
+            ["duplicate-tab-key", "placeholder-image", "empty-tab", "duplicate-description", "placeholder-url", "placeholder-image"],
+            "{}",
+            w.iter().map(|w| w.to_string()).collect::<Vec<_>>().join("\n")
+        );
+        assert!(w[2].message.contains("`audio`"));
+        assert!(w[3].message.contains("tabs `3d`, `3d`"));
+        assert!(w[4].message.contains("github_url"));
+    }
+
+    #[test]
+    fn clean_content_has_no_warnings() {
+        let tabs = [tab("3d", "Rendering experiments", "assets/3d.gif")];
+        let projects = [Project {
+            title: "Alpha".into(),
+            github_url: "https://github.com/8ucchiman/alpha".into(),
+            image_url: "assets/alpha.png".into(),
+            tabs: vec!["3d".into()],
+            start: "2024-05".into(),
+            ..Default::default()
+        }];
+        assert!(check(&tabs, &projects, &Taxonomy::default()).is_empty());
+    }
+}
@@ -0,0 +1,1014 @@
+/*
+ * FileName:        markdown
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 13:05:51
+ * LastModified:    2026-10-18 13:05:51
+ * Reference:       https://spec.commonmark.org/
+ * Description:     依存なしの Markdown → HTML（CommonMark のよく使う部分だけ）
+ */
+
+
+use std::{cell::Cell, collections::BTreeMap};
+
+use crate::{html_attr, html_escape, slugify};
+
+/// Markdown を HTML に変換する。
+///
+/// 対応: 見出し（ATX / Setext）、段落、強調、インラインコード、コードブロック、
+/// リンク・画像（参照リンク含む）、リスト、引用、水平線、HTML のパススルー。
+/// GitHub 拡張: 表、タスクリスト、取り消し線、URL の自動リンク、アラート（`> [!NOTE]`）。
+pub fn to_html(src: &str) -> String {
+    to_html_with(src, &Options::default())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UrlKind {
+    Link,
+    Image,
+}
+
+pub type RewriteUrl<'a> = &'a dyn Fn(&str, UrlKind) -> String;
+
+#[derive(Default)]
+pub struct Options<'a> {
+    /// リンク・画像の URL を書き換える（README の相対パス対策）。
+    /// Markdown のリンクに加え、生 HTML の `href` / `src` にも適用される。
+    pub rewrite_url: Option<RewriteUrl<'a>>,
+}
+
+pub fn to_html_with(src: &str, opts: &Options) -> String {
+    let src = src.replace("\r\n", "\n").replace('\t', "    ");
+    let (lines, refs) = extract_refs(src.lines().map(str::to_string).collect());
+    let md = Markdown { refs, opts, depth: Cell::new(0) };
+    let mut out = String::new();
+    md.blocks(&lines, false, &mut out);
+    out
+}
+
+struct Markdown<'o> {
+    /// 参照リンク定義: ラベル（小文字）→ (URL, title)
+    refs: BTreeMap<String, (String, String)>,
+    opts: &'o Options<'o>,
+    /// 今の入れ子の深さ（ブロックとインラインの合計）
+    depth: Cell<usize>,
+}
+
+/// 入れ子（引用・リスト・強調・リンク）の深さの上限。README は第三者が書くものなので、
+/// `>>>>…` や `[[[[…` のような入力でスタックを使い果たさないよう、これより深い部分はテキストとして出す
+const MAX_DEPTH: usize = 48;
+
+/// GitHub のアラート `> [!NOTE]` の種類
+const ALERTS: &[(&str, &str)] = &[
+    ("NOTE", "Note"),
+    ("TIP", "Tip"),
+    ("IMPORTANT", "Important"),
+    ("WARNING", "Warning"),
+    ("CAUTION", "Caution"),
+];
+
+/// `[label]: url "title"` の行を取り除いて辞書にする（コードブロック内は無視）
+fn extract_refs(lines: Vec<String>) -> (Vec<String>, BTreeMap<String, (String, String)>) {
+    let mut refs = BTreeMap::new();
+    let mut kept = Vec::new();
+    let mut fence: Option<(char, usize)> = None;
+    for line in lines {
+        let t = trim_indent(&line);
+        if let Some((ch, n)) = fence {
+            if is_fence_close(t, ch, n) { fence = None; }
+            kept.push(line);
+            continue;
+        }
+        if let Some((ch, n, _)) = fence_open(t) {
+            fence = Some((ch, n));
+            kept.push(line);
+            continue;
+        }
+        if indent_of(&line) < 4
+            && let Some((label, url, title)) = ref_def(t)
+        {
+            refs.entry(label.to_lowercase()).or_insert((url, title));
+            continue;
+        }
+        kept.push(line);
+    }
+    (kept, refs)
+}
+
+fn ref_def(t: &str) -> Option<(String, String, String)> {
+    let rest = t.strip_prefix('[')?;
+    let close = rest.find("]:")?;
+    let label = &rest[..close];
+    if label.trim().is_empty() || label.contains(['[', ']']) { return None; }
+    let rest = rest[close + 2..].trim();
+    if rest.is_empty() { return None; }
+    let (url, after) = match rest.strip_prefix('<') {
+        Some(r) => { let e = r.find('>')?; (&r[..e], &r[e + 1..]) }
+        None => match rest.find(char::is_whitespace) {
+            Some(e) => (&rest[..e], &rest[e..]),
+            None => (rest, ""),
+        },
+    };
+    let after = after.trim();
+    let title = if after.is_empty() {
+        String::new()
+    } else {
+        let (open, close) = match after.chars().next()? {
+            '"' => ('"', '"'),
+            '\'' => ('\'', '\''),
+            '(' => ('(', ')'),
+            _ => return None,
+        };
+        after.strip_prefix(open)?.strip_suffix(close)?.to_string()
+    };
+    Some((label.to_string(), url.to_string(), title))
+}
+
+fn fence_open(t: &str) -> Option<(char, usize, &str)> {
+    let ch = t.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let n = t.chars().take_while(|c| *c == ch).count();
+    if n < 3 { return None; }
+    let info = t[n..].trim();
+    if ch == '`' && info.contains('`') { return None; }
+    Some((ch, n, info))
+}
+
+fn is_fence_close(t: &str, ch: char, n: usize) -> bool {
+    let run = t.chars().take_while(|c| *c == ch).count();
+    run >= n && t[run..].trim().is_empty()
+}
+
+fn atx_heading(t: &str) -> Option<(usize, &str)> {
+    let level = t.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) { return None; }
+    let rest = &t[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') { return None; }
+    // 末尾の閉じ `#` は取り除く
+    let mut text = rest.trim();
+    let stripped = text.trim_end_matches('#');
+    if stripped.is_empty() || stripped.ends_with(' ') { text = stripped.trim_end(); }
+    Some((level, text))
+}
+
+fn is_hr(t: &str) -> bool {
+    let t: String = t.chars().filter(|c| !c.is_whitespace()).collect();
+    t.len() >= 3 && ["-", "*", "_"].iter().any(|m| t.chars().all(|c| c.to_string() == *m))
+}
+
+fn setext_level(t: &str) -> Option<usize> {
+    let t = t.trim();
+    if t.is_empty() { return None; }
+    if t.chars().all(|c| c == '=') { return Some(1); }
+    if t.chars().all(|c| c == '-') { return Some(2); }
+    None
+}
+
+fn is_html_block(t: &str) -> bool {
+    let Some(rest) = t.strip_prefix('<') else { return false };
+    rest.starts_with("!--")
+        || rest.starts_with('/') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        || rest.starts_with(|c: char| c.is_ascii_alphabetic())
+            && rest.find(['>', ' ']).is_some()
+            && !rest.starts_with("http")
+}
+
+/// リスト項目の先頭
+#[derive(Clone, Copy)]
+struct ListMarker {
+    ordered: bool,
+    /// `-` `*` `+`、または番号の後ろの `.` `)`
+    delim: char,
+    start: u64,
+    /// 行頭から本文までの桁数（続きの行のインデント）
+    content: usize,
+    /// マーカーの行で本文が始まるバイト位置
+    body: usize,
+}
+
+fn list_marker(line: &str) -> Option<ListMarker> {
+    let t = trim_indent(line);
+    let indent = indent_of(line);
+    let (ordered, delim, start, mlen) = match t.chars().next()? {
+        c @ ('-' | '*' | '+') => (false, c, 0, 1),
+        c if c.is_ascii_digit() => {
+            let digits = t.chars().take_while(char::is_ascii_digit).count();
+            if digits > 9 { return None; }
+            let d = t[digits..].chars().next().filter(|c| *c == '.' || *c == ')')?;
+            (true, d, t[..digits].parse().ok()?, digits + 1)
+        }
+        _ => return None,
+    };
+    let after = &t[mlen..];
+    if !after.is_empty() && !after.starts_with(' ') { return None; }
+    let spaces = after.chars().take_while(|c| *c == ' ').count();
+    // 本文の前の空白が 5 以上ならインデントコード扱いなので 1 とみなす
+    let pad = if after.trim().is_empty() || spaces > 4 { 1 } else { spaces };
+    Some(ListMarker { ordered, delim, start, content: indent + mlen + pad, body: line.len() - t.len() + mlen + pad })
+}
+
+/// 行頭のインデント（ASCII の空白とタブだけ。全角空白などは本文として残す）を落とす
+fn trim_indent(line: &str) -> &str {
+    line.trim_start_matches([' ', '\t'])
+}
+
+/// 行頭のインデントの桁数（タブは次の 4 の倍数の桁まで進める）
+fn indent_of(line: &str) -> usize {
+    leading(line, usize::MAX).0
+}
+
+/// 行頭のインデントを最大 n 桁まで読み、(桁数, バイト数) を返す
+fn leading(line: &str, n: usize) -> (usize, usize) {
+    let mut col = 0;
+    for (i, b) in line.bytes().enumerate() {
+        let next = match b {
+            b' ' => col + 1,
+            b'\t' => col + 4 - col % 4,
+            _ => return (col, i),
+        };
+        if next > n {
+            return (col, i);
+        }
+        col = next;
+    }
+    (col, line.len())
+}
+
+/// 先頭から最大 n 桁のインデントを落とす（n をまたぐタブは残りの桁を空白にする）
+fn dedent(line: &str, n: usize) -> String {
+    let (col, k) = leading(line, n);
+    match line[k..].strip_prefix('\t') {
+        Some(rest) if col < n => format!("{}{rest}", " ".repeat(col + 4 - col % 4 - n)),
+        _ => line[k..].to_string(),
+    }
+}
+
+/// 表の行をセルに分ける（先頭・末尾の `|` は無視、`\|` とコードスパン内の `|` は区切らない）
+fn table_cells(line: &str) -> Vec<String> {
+    let t = line.trim();
+    let t = t.strip_prefix('|').unwrap_or(t);
+    let t = if t.ends_with('|') && !t.ends_with("\\|") { &t[..t.len() - 1] } else { t };
+    let mut cells = Vec::new();
+    let mut cur = String::new();
+    let mut in_code = false;
+    let mut chars = t.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => { cur.push('|'); chars.next(); }
+            '`' => { in_code = !in_code; cur.push(c); }
+            '|' if !in_code => cells.push(std::mem::take(&mut cur).trim().to_string()),
+            _ => cur.push(c),
+        }
+    }
+    cells.push(cur.trim().to_string());
+    cells
+}
+
+/// 表の区切り行（`|---|:--:|`）なら各列の揃えを返す
+fn table_delims(line: &str) -> Option<Vec<Option<&'static str>>> {
+    if !line.contains('-') || !line.trim().chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')) {
+        return None;
+    }
+    table_cells(line)
+        .iter()
+        .map(|c| {
+            let dashes = c.trim_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|x| x == '-') { return None; }
+            Some(match (c.starts_with(':'), c.ends_with(':')) {
+                (true, true) => Some("center"),
+                (true, false) => Some("left"),
+                (false, true) => Some("right"),
+                (false, false) => None,
+            })
+        })
+        .collect()
+}
+
+impl Markdown<'_> {
+    fn url(&self, url: &str, kind: UrlKind) -> String {
+        match self.opts.rewrite_url {
+            Some(f) => f(url, kind),
+            None => url.to_string(),
+        }
+    }
+
+    /// 生 HTML の `href="..."` / `src="..."` を書き換える
+    fn rewrite_html(&self, html: &str) -> String {
+        if self.opts.rewrite_url.is_none() {
+            return html.to_string();
+        }
+        let mut out = String::new();
+        let mut rest = html;
+        loop {
+            let next = [(" href=", UrlKind::Link), (" src=", UrlKind::Image)]
+                .iter()
+                .filter_map(|&(a, k)| rest.find(a).map(|at| (at, a, k)))
+                .min_by_key(|x| x.0);
+            let Some((at, attr, kind)) = next else { break };
+            let after = &rest[at + attr.len()..];
+            let Some(q @ ('"' | '\'')) = after.chars().next() else {
+                out.push_str(&rest[..at + attr.len()]);
+                rest = after;
+                continue;
+            };
+            let Some(end) = after[1..].find(q) else { break };
+            let value = after[1..1 + end].replace("&amp;", "&");
+            // 書き換えた URL に `'` が入っても属性から抜け出さないよう、元の引用符によらず `"` で囲む
+            out.push_str(&rest[..at + attr.len()]);
+            out.push('"');
+            out.push_str(&html_attr(&self.url(&value, kind)));
+            out.push('"');
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// 段落の途中で新しいブロックが始まるか（段落を打ち切る条件）
+    fn interrupts(&self, line: &str) -> bool {
+        let t = trim_indent(line);
+        if indent_of(line) >= 4 { return false; }
+        atx_heading(t).is_some()
+            || fence_open(t).is_some()
+            || is_hr(t)
+            || t.starts_with('>')
+            || is_html_block(t)
+            || list_marker(line).is_some_and(|m| !line.get(m.body..).unwrap_or("").trim().is_empty() && (!m.ordered || m.start == 1))
+    }
+
+    /// 入れ子の深さを 1 つ進めて `f` を呼ぶ。上限に達していれば None
+    fn nested<T>(&self, f: impl FnOnce() -> T) -> Option<T> {
+        let depth = self.depth.get();
+        if depth >= MAX_DEPTH {
+            return None;
+        }
+        self.depth.set(depth + 1);
+        let r = f();
+        self.depth.set(depth);
+        Some(r)
+    }
+
+    fn blocks(&self, lines: &[String], tight: bool, out: &mut String) {
+        let mut nested = String::new();
+        match self.nested(|| self.block_list(lines, tight, &mut nested)) {
+            Some(()) => out.push_str(&nested),
+            None => out.push_str(&format!("<p>{}</p>\n", html_escape(lines.join("\n").trim()))),
+        }
+    }
+
+    fn block_list(&self, lines: &[String], tight: bool, out: &mut String) {
+        let mut i = 0;
+        while i < lines.len() {
+            let line = &lines[i];
+            let t = trim_indent(line);
+            let indent = indent_of(line);
+            if t.is_empty() {
+                i += 1;
+                continue;
+            }
+
+            // インデントによるコードブロック
+            if indent >= 4 {
+                let mut code: Vec<String> = Vec::new();
+                while i < lines.len() && (indent_of(&lines[i]) >= 4 || lines[i].trim().is_empty()) {
+                    code.push(dedent(&lines[i], 4));
+                    i += 1;
+                }
+                while code.last().is_some_and(|l| l.trim().is_empty()) { code.pop(); }
+                out.push_str(&format!("<pre><code>{}\n</code></pre>\n", html_escape(&code.join("\n"))));
+                continue;
+            }
+
+            // フェンスコードブロック
+            if let Some((ch, n, info)) = fence_open(t) {
+                let lang = info.split_whitespace().next().unwrap_or("");
+                let mut code = Vec::new();
+                i += 1;
+                while i < lines.len() && !is_fence_close(trim_indent(&lines[i]), ch, n) {
+                    code.push(dedent(&lines[i], indent));
+                    i += 1;
+                }
+                i += 1; // 閉じフェンス
+                let class = if lang.is_empty() { String::new() } else { format!(r#" class="language-{}""#, html_attr(lang)) };
+                let body = if code.is_empty() { String::new() } else { format!("{}\n", html_escape(&code.join("\n"))) };
+                out.push_str(&format!("<pre><code{class}>{body}</code></pre>\n"));
+                continue;
+            }
+
+            if let Some((level, text)) = atx_heading(t) {
+                self.heading(level, text, out);
+                i += 1;
+                continue;
+            }
+
+            if is_hr(t) {
+                out.push_str("<hr>\n");
+                i += 1;
+                continue;
+            }
+
+            // 引用
+            if t.starts_with('>') {
+                let mut inner = Vec::new();
+                while i < lines.len() {
+                    let l = trim_indent(&lines[i]);
+                    if let Some(rest) = l.strip_prefix('>') {
+                        inner.push(rest.strip_prefix(' ').unwrap_or(rest).to_string());
+                    } else if !l.is_empty() && !inner.last().is_none_or(|p: &String| p.trim().is_empty()) && !self.interrupts(&lines[i]) {
+                        inner.push(l.to_string()); // 遅延継続行
+                    } else {
+                        break;
+                    }
+                    i += 1;
+                }
+                let alert = inner.first().and_then(|first| {
+                    let kind = first.trim().strip_prefix("[!")?.strip_suffix(']')?.to_ascii_uppercase();
+                    ALERTS.iter().find(|(k, _)| *k == kind)
+                });
+                match alert {
+                    Some((kind, title)) => {
+                        out.push_str(&format!(
+                            "<div class=\"markdown-alert markdown-alert-{}\">\n<p class=\"markdown-alert-title\">{title}</p>\n",
+                            kind.to_ascii_lowercase()
+                        ));
+                        self.blocks(&inner[1..], false, out);
+                        out.push_str("</div>\n");
+                    }
+                    None => {
+                        out.push_str("<blockquote>\n");
+                        self.blocks(&inner, false, out);
+                        out.push_str("</blockquote>\n");
+                    }
+                }
+                continue;
+            }
+
+            if let Some(m) = list_marker(line) {
+                i = self.list(lines, i, m, out);
+                continue;
+            }
+
+            if is_html_block(t) {
+                while i < lines.len() && !lines[i].trim().is_empty() {
+                    out.push_str(&self.rewrite_html(&lines[i]));
+                    out.push('\n');
+                    i += 1;
+                }
+                continue;
+            }
+
+            // 表（ヘッダ行 + 区切り行）
+            if t.contains('|')
+                && let Some(aligns) = lines.get(i + 1).and_then(|l| table_delims(l))
+                && table_cells(t).len() == aligns.len()
+            {
+                let cell = |tag: &str, text: &str, col: usize| {
+                    let align = aligns.get(col).copied().flatten().map(|a| format!(r#" style="text-align:{a}""#)).unwrap_or_default();
+                    format!("<{tag}{align}>{}</{tag}>", self.inline(text))
+                };
+                out.push_str("<table>\n<thead>\n<tr>");
+                for (col, h) in table_cells(t).iter().enumerate() {
+                    out.push_str(&cell("th", h, col));
+                }
+                out.push_str("</tr>\n</thead>\n");
+                i += 2;
+                let mut body = String::new();
+                while i < lines.len() && !lines[i].trim().is_empty() && !self.interrupts(&lines[i]) {
+                    let cells = table_cells(&lines[i]);
+                    body.push_str("<tr>");
+                    for col in 0..aligns.len() {
+                        body.push_str(&cell("td", cells.get(col).map(String::as_str).unwrap_or(""), col));
+                    }
+                    body.push_str("</tr>\n");
+                    i += 1;
+                }
+                if !body.is_empty() {
+                    out.push_str(&format!("<tbody>\n{body}</tbody>\n"));
+                }
+                out.push_str("</table>\n");
+                continue;
+            }
+
+            // 段落（Setext 見出しもここで拾う）
+            let mut para = vec![t.to_string()];
+            i += 1;
+            let mut setext = None;
+            while i < lines.len() {
+                let l = &lines[i];
+                if l.trim().is_empty() { break; }
+                if indent_of(l) < 4 && let Some(level) = setext_level(l) {
+                    setext = Some(level);
+                    i += 1;
+                    break;
+                }
+                if self.interrupts(l) { break; }
+                para.push(trim_indent(l).to_string());
+                i += 1;
+            }
+            let text = para.join("\n");
+            match setext {
+                Some(level) => self.heading(level, text.trim(), out),
+                None if tight => { out.push_str(&self.inline(text.trim_end())); out.push('\n'); }
+                None => out.push_str(&format!("<p>{}</p>\n", self.inline(text.trim_end()))),
+            }
+        }
+    }
+
+    fn heading(&self, level: usize, text: &str, out: &mut String) {
+        let id = slugify(&strip_tags(&self.inline(text)));
+        out.push_str(&format!(r#"<h{level} id="{}">{}</h{level}>"#, html_attr(&id), self.inline(text)));
+        out.push('\n');
+    }
+
+    /// リスト全体を読んで出力し、次の行番号を返す
+    fn list(&self, lines: &[String], mut i: usize, first: ListMarker, out: &mut String) -> usize {
+        let mut items: Vec<Vec<String>> = Vec::new();
+        let mut loose = false;
+        while i < lines.len() {
+            let Some(m) = list_marker(&lines[i]) else { break };
+            if m.ordered != first.ordered || m.delim != first.delim { break; }
+            let mut item = vec![lines[i].get(m.body..).unwrap_or("").to_string()];
+            i += 1;
+            while i < lines.len() {
+                let l = &lines[i];
+                if l.trim().is_empty() {
+                    item.push(String::new());
+                } else if indent_of(l) >= m.content {
+                    item.push(dedent(l, m.content));
+                } else if item.last().is_some_and(|p| !p.trim().is_empty()) && !self.interrupts(l) && list_marker(l).is_none() {
+                    item.push(trim_indent(l).to_string()); // 遅延継続行
+                } else {
+                    break;
+                }
+                i += 1;
+            }
+            // 項目末尾の空行は項目間の空行（= loose）
+            let mut trailing_blank = false;
+            while item.last().is_some_and(|l| l.trim().is_empty()) {
+                item.pop();
+                trailing_blank = true;
+            }
+            if item.iter().any(|l| l.trim().is_empty()) { loose = true; }
+            items.push(item);
+            if trailing_blank {
+                if i < lines.len() && list_marker(&lines[i]).is_some_and(|n| n.ordered == first.ordered && n.delim == first.delim) {
+                    loose = true;
+                } else {
+                    break;
+                }
+            }
+        }
+        let open = if first.ordered {
+            if first.start == 1 { "<ol>".to_string() } else { format!(r#"<ol start="{}">"#, first.start) }
+        } else {
+            "<ul>".to_string()
+        };
+        out.push_str(&open);
+        out.push('\n');
+        for item in &items {
+            // タスクリスト `- [ ] todo` / `- [x] done`
+            let mut item = item.clone();
+            let task = item.first().and_then(|first| {
+                let done = first.starts_with("[x] ") || first.starts_with("[X] ");
+                (done || first.starts_with("[ ] ")).then_some(done)
+            });
+            if task.is_some() {
+                item[0] = item[0][4..].to_string();
+            }
+            let mut inner = String::new();
+            self.blocks(&item, !loose, &mut inner);
+            match task {
+                Some(done) => out.push_str(&format!(
+                    r#"<li class="task-list-item"><input type="checkbox" disabled{}> "#,
+                    if done { " checked" } else { "" }
+                )),
+                None => out.push_str("<li>"),
+            }
+            out.push_str(inner.strip_suffix('\n').unwrap_or(&inner));
+            out.push_str("</li>\n");
+        }
+        out.push_str(if first.ordered { "</ol>\n" } else { "</ul>\n" });
+        i
+    }
+
+    // ---- inline ----
+    fn inline(&self, s: &str) -> String {
+        self.nested(|| self.spans(s)).unwrap_or_else(|| html_escape(s))
+    }
+
+    fn spans(&self, s: &str) -> String {
+        let c: Vec<char> = s.chars().collect();
+        // 各位置から見て次の `>` か改行の位置（`<` のたびに残り全体を探さないよう、先に 1 回で求める）
+        let mut tag_end = vec![c.len(); c.len() + 1];
+        for j in (0..c.len()).rev() {
+            tag_end[j] = if matches!(c[j], '>' | '\n') { j } else { tag_end[j + 1] };
+        }
+        let mut out = String::new();
+        let mut i = 0;
+        while i < c.len() {
+            match c[i] {
+                '\\' if c.get(i + 1).is_some_and(|n| n.is_ascii_punctuation()) => {
+                    out.push_str(&html_escape(&c[i + 1].to_string()));
+                    i += 2;
+                }
+                '\\' if c.get(i + 1) == Some(&'\n') => {
+                    out.push_str("<br>\n");
+                    i += 2;
+                }
+                '`' => {
+                    let n = run(&c, i, '`');
+                    match find_code_close(&c, i + n, n) {
+                        Some(j) => {
+                            let code: String = c[i + n..j].iter().collect::<String>().replace('\n', " ");
+                            let code = if code.len() > 2 && code.starts_with(' ') && code.ends_with(' ') && !code.trim().is_empty() {
+                                &code[1..code.len() - 1]
+                            } else {
+                                &code
+                            };
+                            out.push_str(&format!("<code>{}</code>", html_escape(code)));
+                            i = j + n;
+                        }
+                        None => {
+                            out.push_str(&"`".repeat(n));
+                            i += n;
+                        }
+                    }
+                }
+                '!' if c.get(i + 1) == Some(&'[') => match self.link(&c, i + 1) {
+                    Some((text, url, title, end)) => {
+                        let t = if title.is_empty() { String::new() } else { format!(r#" title="{}""#, html_attr(&title)) };
+                        out.push_str(&format!(
+                            r#"<img src="{}" alt="{}"{t}>"#,
+                            html_attr(&self.url(&url, UrlKind::Image)), html_attr(&strip_tags(&self.inline(&text)))
+                        ));
+                        i = end;
+                    }
+                    None => { out.push('!'); i += 1; }
+                },
+                '[' => match self.link(&c, i) {
+                    Some((text, url, title, end)) => {
+                        let t = if title.is_empty() { String::new() } else { format!(r#" title="{}""#, html_attr(&title)) };
+                        out.push_str(&format!(r#"<a href="{}"{t}>{}</a>"#, html_attr(&self.url(&url, UrlKind::Link)), self.inline(&text)));
+                        i = end;
+                    }
+                    None => { out.push('['); i += 1; }
+                },
+                '<' => {
+                    // 自動リンクもインラインのタグも改行を含まないので、同じ行の `>` までだけ見る
+                    let end = tag_end[i + 1];
+                    if c.get(end) == Some(&'>') {
+                        let tag: String = c[i..=end].iter().collect();
+                        let inner = &tag[1..tag.len() - 1];
+                        if is_autolink(inner) {
+                            let href = if inner.contains(':') { inner.to_string() } else { format!("mailto:{inner}") };
+                            out.push_str(&format!(r#"<a href="{}">{}</a>"#, html_attr(&href), html_escape(inner)));
+                            i = end + 1;
+                            continue;
+                        }
+                        if is_inline_tag(inner) {
+                            out.push_str(&self.rewrite_html(&tag));
+                            i = end + 1;
+                            continue;
+                        }
+                    }
+                    out.push_str("&lt;");
+                    i += 1;
+                }
+                d @ ('*' | '_') => {
+                    let n = run(&c, i, d);
+                    let prev = if i == 0 { ' ' } else { c[i - 1] };
+                    let next = c.get(i + n).copied().unwrap_or(' ');
+                    // `_` は単語の途中では強調にしない（snake_case 対策）
+                    let can_open = !(next.is_whitespace() || d == '_' && prev.is_alphanumeric());
+                    let closed = if !can_open {
+                        None
+                    } else if n >= 2 {
+                        find_emph_close(&c, i + 2, d, 2).map(|j| (j, 2, "strong"))
+                    } else {
+                        find_emph_close(&c, i + 1, d, 1).map(|j| (j, 1, "em"))
+                    };
+                    match closed {
+                        Some((j, k, tag)) => {
+                            let inner: String = c[i + k..j].iter().collect();
+                            out.push_str(&format!("<{tag}>{}</{tag}>", self.inline(&inner)));
+                            i = j + k;
+                        }
+                        None => {
+                            out.push_str(&d.to_string().repeat(n));
+                            i += n;
+                        }
+                    }
+                }
+                '&' => {
+                    let rest: String = c[i..c.len().min(i + 12)].iter().collect();
+                    match entity_len(&rest) {
+                        Some(n) => { out.push_str(&rest[..n]); i += rest[..n].chars().count(); }
+                        None => { out.push_str("&amp;"); i += 1; }
+                    }
+                }
+                '\n' => {
+                    // 行末の 2 つ以上の空白は改行
+                    if out.ends_with("  ") {
+                        while out.ends_with(' ') { out.pop(); }
+                        out.push_str("<br>\n");
+                    } else {
+                        while out.ends_with(' ') { out.pop(); }
+                        out.push('\n');
+                    }
+                    i += 1;
+                }
+                '~' if run(&c, i, '~') == 2 => {
+                    let close = (i + 3..c.len().saturating_sub(1)).find(|&j| c[j] == '~' && c[j + 1] == '~' && !c[j - 1].is_whitespace());
+                    match close {
+                        Some(j) if !c[i + 2].is_whitespace() => {
+                            let inner: String = c[i + 2..j].iter().collect();
+                            out.push_str(&format!("<del>{}</del>", self.inline(&inner)));
+                            i = j + 2;
+                        }
+                        _ => { out.push_str("~~"); i += 2; }
+                    }
+                }
+                'h' | 'w' if (i == 0 || !c[i - 1].is_alphanumeric()) && autolink_literal(&c[i..]).is_some() => {
+                    let n = autolink_literal(&c[i..]).unwrap_or(0);
+                    let text: String = c[i..i + n].iter().collect();
+                    let href = if text.starts_with("www.") { format!("http://{text}") } else { text.clone() };
+                    out.push_str(&format!(r#"<a href="{}">{}</a>"#, html_attr(&href), html_escape(&text)));
+                    i += n;
+                }
+                '>' => { out.push_str("&gt;"); i += 1; }
+                ch => { out.push(ch); i += 1; }
+            }
+        }
+        out
+    }
+
+    /// `[text](url "title")` / `[text][ref]` / `[ref]` を読む。(text, url, title, 終端) を返す。
+    fn link(&self, c: &[char], open: usize) -> Option<(String, String, String, usize)> {
+        // 対応する `]` を探す（入れ子とコードスパンを考慮）
+        let mut depth = 0;
+        let mut j = open;
+        let close = loop {
+            match c.get(j)? {
+                '\\' => j += 1,
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 { break j; }
+                }
+                _ => {}
+            }
+            j += 1;
+        };
+        let text: String = c[open + 1..close].iter().collect();
+        match c.get(close + 1) {
+            Some('(') => {
+                let mut k = close + 2;
+                while c.get(k).is_some_and(|x| x.is_whitespace()) { k += 1; }
+                let mut url = String::new();
+                if c.get(k) == Some(&'<') {
+                    k += 1;
+                    while *c.get(k)? != '>' { url.push(c[k]); k += 1; }
+                    k += 1;
+                } else {
+                    let mut parens = 0;
+                    while let Some(&x) = c.get(k) {
+                        if x.is_whitespace() { break; }
+                        if x == '(' { parens += 1; }
+                        if x == ')' {
+                            if parens == 0 { break; }
+                            parens -= 1;
+                        }
+                        if x == '\\' && c.get(k + 1).is_some_and(|n| n.is_ascii_punctuation()) {
+                            k += 1;
+                            url.push(c[k]);
+                        } else {
+                            url.push(x);
+                        }
+                        k += 1;
+                    }
+                }
+                while c.get(k).is_some_and(|x| x.is_whitespace()) { k += 1; }
+                let mut title = String::new();
+                if let Some(&q @ ('"' | '\'' | '(')) = c.get(k) {
+                    let end = if q == '(' { ')' } else { q };
+                    k += 1;
+                    while *c.get(k)? != end { title.push(c[k]); k += 1; }
+                    k += 1;
+                    while c.get(k).is_some_and(|x| x.is_whitespace()) { k += 1; }
+                }
+                if c.get(k) != Some(&')') { return None; }
+                Some((text, url, title, k + 1))
+            }
+            Some('[') => {
+                let end = c[close + 2..].iter().position(|x| *x == ']')? + close + 2;
+                let label: String = c[close + 2..end].iter().collect();
+                let label = if label.is_empty() { text.clone() } else { label };
+                let (url, title) = self.refs.get(&label.to_lowercase())?;
+                Some((text, url.clone(), title.clone(), end + 1))
+            }
+            _ => {
+                let (url, title) = self.refs.get(&text.to_lowercase())?;
+                Some((text.clone(), url.clone(), title.clone(), close + 1))
+            }
+        }
+    }
+}
+
+fn run(c: &[char], i: usize, ch: char) -> usize {
+    c[i..].iter().take_while(|x| **x == ch).count()
+}
+
+fn find_code_close(c: &[char], from: usize, n: usize) -> Option<usize> {
+    let mut j = from;
+    while j < c.len() {
+        if c[j] == '`' {
+            let r = run(c, j, '`');
+            if r == n { return Some(j); }
+            j += r;
+        } else {
+            j += 1;
+        }
+    }
+    None
+}
+
+/// 閉じ側の強調記号を探す。長さ `n` ちょうどの連なりで、直前が空白でないもの。
+fn find_emph_close(c: &[char], from: usize, d: char, n: usize) -> Option<usize> {
+    let mut j = from;
+    while j < c.len() {
+        match c[j] {
+            '\\' => j += 2,
+            '`' => {
+                let r = run(c, j, '`');
+                j = find_code_close(c, j + r, r).map(|k| k + r).unwrap_or(j + r);
+            }
+            x if x == d => {
+                let r = run(c, j, d);
+                let after = c.get(j + r).copied().unwrap_or(' ');
+                let intraword = d == '_' && after.is_alphanumeric();
+                if j > from && !c[j - 1].is_whitespace() && !intraword && (r == n || (n == 2 && r == 3)) {
+                    return Some(if r == 3 { j + 1 } else { j });
+                }
+                j += r;
+            }
+            _ => j += 1,
+        }
+    }
+    None
+}
+
+fn is_autolink(s: &str) -> bool {
+    if s.contains(char::is_whitespace) || s.is_empty() { return false; }
+    if let Some((scheme, _)) = s.split_once(':') {
+        return scheme.len() >= 2 && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c));
+    }
+    s.split_once('@').is_some_and(|(u, d)| !u.is_empty() && d.contains('.'))
+}
+
+/// 本文中の `https://...` / `www.` の長さ（末尾の句読点は含めない）
+fn autolink_literal(c: &[char]) -> Option<usize> {
+    let head: String = c.iter().take(8).collect();
+    if !(head.starts_with("https://") || head.starts_with("http://") || head.starts_with("www.")) {
+        return None;
+    }
+    let mut n = c.iter().take_while(|x| !x.is_whitespace() && **x != '<').count();
+    while n > 0 && matches!(c[n - 1], '.' | ',' | ':' | ';' | '!' | '?' | '*' | '_' | '~' | '\'' | '"') {
+        n -= 1;
+    }
+    // 閉じ括弧は対応する開き括弧が無ければ含めない
+    while n > 0 && c[n - 1] == ')' && c[..n].iter().filter(|x| **x == '(').count() < c[..n].iter().filter(|x| **x == ')').count() {
+        n -= 1;
+    }
+    let min = if head.starts_with("www.") { 5 } else { 9 };
+    (n >= min).then_some(n)
+}
+
+fn is_inline_tag(s: &str) -> bool {
+    let s = s.strip_prefix('/').unwrap_or(s);
+    s.starts_with("!--") || s.starts_with(|c: char| c.is_ascii_alphabetic()) && !s.contains('\n')
+}
+
+/// `&amp;` `&#123;` `&#x1F;` のような実体参照の長さ
+fn entity_len(s: &str) -> Option<usize> {
+    let end = s.find(';')?;
+    let body = &s[1..end];
+    let ok = match body.strip_prefix('#') {
+        Some(num) => match num.strip_prefix(['x', 'X']) {
+            Some(hex) => !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            None => !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()),
+        },
+        None => !body.is_empty() && body.chars().all(|c| c.is_ascii_alphanumeric()),
+    };
+    ok.then_some(end + 1)
+}
+
+/// タグを除いたテキスト（alt 属性や見出し id 用）
+pub fn strip_tags(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    out.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_width_space_indent_stays_in_paragraph() {
+        assert_eq!(to_html("\u{3000}\u{3000}x\n"), "<p>\u{3000}\u{3000}x</p>\n");
+    }
+
+    #[test]
+    fn full_width_space_indent_in_list_item() {
+        assert_eq!(to_html("- a\n\u{3000}b\n"), "<ul>\n<li>a\n\u{3000}b</li>\n</ul>\n");
+    }
+
+    #[test]
+    fn rewritten_html_attributes_are_double_quoted() {
+        let rewrite = |url: &str, _: UrlKind| format!("https://example.com/{url}?q='x'");
+        let opts = Options { rewrite_url: Some(&rewrite) };
+        assert_eq!(
+            to_html_with("<img src='a.png' alt='a'>\n", &opts),
+            "<img src=\"https://example.com/a.png?q='x'\" alt='a'>\n"
+        );
+    }
+
+    #[test]
+    fn tables_with_alignment_and_escaped_pipes() {
+        let md = "| a | b | c |\n|:--|:-:|--:|\n| `x|y` | a\\|b | **z** |\n| short |\n";
+        assert_eq!(
+            to_html(md),
+            "<table>\n<thead>\n<tr><th style=\"text-align:left\">a</th><th style=\"text-align:center\">b</th><th style=\"text-align:right\">c</th></tr>\n</thead>\n\
+             <tbody>\n<tr><td style=\"text-align:left\"><code>x|y</code></td><td style=\"text-align:center\">a|b</td><td style=\"text-align:right\"><strong>z</strong></td></tr>\n\
+             <tr><td style=\"text-align:left\">short</td><td style=\"text-align:center\"></td><td style=\"text-align:right\"></td></tr>\n</tbody>\n</table>\n"
+        );
+        // 区切り行と列の数が合わなければ表にしない
+        assert_eq!(to_html("a | b\n--|--|--\n"), "<p>a | b\n--|--|--</p>\n");
+    }
+
+    #[test]
+    fn task_lists() {
+        assert_eq!(
+            to_html("- [ ] todo\n- [x] done\n- [X] also\n- plain\n"),
+            "<ul>\n<li class=\"task-list-item\"><input type=\"checkbox\" disabled> todo</li>\n\
+             <li class=\"task-list-item\"><input type=\"checkbox\" disabled checked> done</li>\n\
+             <li class=\"task-list-item\"><input type=\"checkbox\" disabled checked> also</li>\n<li>plain</li>\n</ul>\n"
+        );
+    }
+
+    #[test]
+    fn alerts() {
+        assert_eq!(
+            to_html("> [!warning]\n> Be *careful*.\n"),
+            "<div class=\"markdown-alert markdown-alert-warning\">\n<p class=\"markdown-alert-title\">Warning</p>\n<p>Be <em>careful</em>.</p>\n</div>\n"
+        );
+        // 知らない種類はただの引用
+        assert_eq!(to_html("> [!FOO]\n> x\n"), "<blockquote>\n<p>[!FOO]\nx</p>\n</blockquote>\n");
+    }
+
+    #[test]
+    fn links_and_images_are_rewritten() {
+        let rewrite = |url: &str, kind: UrlKind| format!("{}:{url}", if kind == UrlKind::Image { "img" } else { "a" });
+        let opts = Options { rewrite_url: Some(&rewrite) };
+        let md = "[doc](docs/a.md \"Doc\") ![logo](logo.png) [ref][r] <a href=\"x.md\">x</a>\n\n[r]: ref.md\n";
+        assert_eq!(
+            to_html_with(md, &opts),
+            "<p><a href=\"a:docs/a.md\" title=\"Doc\">doc</a> <img src=\"img:logo.png\" alt=\"logo\"> <a href=\"a:ref.md\">ref</a> <a href=\"a:x.md\">x</a></p>\n"
+        );
+        // 自動リンクは書き換えない
+        assert_eq!(to_html_with("<https://e.com> www.e.com\n", &opts), "<p><a href=\"https://e.com\">https://e.com</a> <a href=\"http://www.e.com\">www.e.com</a></p>\n");
+    }
+
+    #[test]
+    fn deep_nesting_is_cut_off_as_text() {
+        let quotes = format!("{}x\n", ">".repeat(10_000));
+        let html = to_html(&quotes);
+        assert_eq!(html.matches("<blockquote>").count(), MAX_DEPTH);
+        assert!(html.contains("&gt;&gt;&gt;x"));
+
+        let links = format!("{}x{}\n", "[".repeat(2_000), "](u)".repeat(2_000));
+        assert!(to_html(&links).contains("<a href=\"u\">"));
+        let emphasis = format!("{}x{}\n", "*a ".repeat(2_000), " a*".repeat(2_000));
+        assert!(to_html(&emphasis).starts_with("<p><em>a "));
+    }
+
+    #[test]
+    fn many_unclosed_angle_brackets_are_linear() {
+        let html = to_html(&format!("x {}\n", "<a ".repeat(100_000)));
+        assert_eq!(html.matches("&lt;a").count(), 100_000);
+    }
+
+    #[test]
+    fn dedent_counts_columns() {
+        assert_eq!(indent_of("  \u{3000}x"), 2);
+        assert_eq!(dedent("      x", 4), "  x");
+        assert_eq!(dedent("  \u{3000}x", 4), "\u{3000}x");
+        assert_eq!(dedent("  \tx", 3), " x");
+    }
+}
@@ -0,0 +1,49 @@
+/*
+ * FileName:        pool
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 21:12:44
+ * LastModified:    2026-10-19 21:12:44
+ * Reference:       8ucchiman.github.io
+ * Description:     ページ・アセットの並列処理（std::thread::scope と共有の作業キュー）
+ */
+
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// 既定の並列数（使える CPU の数）
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// `items` の各要素に `f` をかける。最大 `jobs` 本のスレッドが共有のキュー（次に取る添字）から 1 件ずつ取って処理する。
+/// 結果は処理の順番に依らず `items` と同じ順に並ぶ
+pub fn map<T: Sync, R: Send>(jobs: usize, items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<R>>> = items.iter().map(|_| Mutex::new(None)).collect();
+    thread::scope(|s| {
+        for _ in 0..jobs {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else { break };
+                let r = f(item);
+                *results[i].lock().unwrap() = Some(r);
+            });
+        }
+    });
+    results.into_iter().map(|r| r.into_inner().unwrap().unwrap()).collect()
+}
+
+/// map() の Result 版。エラーは（先に失敗したものではなく）`items` の順で最初のものを返す
+pub fn try_map<T: Sync, R: Send, E: Send>(jobs: usize, items: &[T], f: impl Fn(&T) -> Result<R, E> + Sync) -> Result<Vec<R>, E> {
+    map(jobs, items, f).into_iter().collect()
+}
@@ -0,0 +1,205 @@
+/*
+ * FileName:        projects
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 14:21:36
+ * LastModified:    2026-10-18 14:21:36
+ * Reference:       8ucchiman.github.io
+ * Description:     Project ごとの詳細ページ（dist/projects/<slug>/index.html）
+ */
+
+
+use std::{collections::BTreeSet, fs, io, path::{Path, PathBuf}};
+
+use crate::{cache::{Fingerprint, Incremental}, error::BuildError, add_card_inputs, asset_url, html_attr, html_escape, import, markdown, pool, readme, project_badges_html, project_card_html, slugify, subpage, taxonomy::Taxonomy, url_path, Files, Project};
+
+/// 各 Project の詳細ページを出力に加える。
+/// 本文は `content_dir/<slug>.md` があれば Markdown として描画する。
+/// `related[i]` は i 番目の Project の関連 Project（related::compute の結果）。
+/// ページは最大 `jobs` 並列で描画し、出力・警告・エラーは Project の順にまとめる。
+/// README を使わないページは、入力（Project・前後・関連・taxonomy・本文）が前回と同じなら `inc` から使い回す。
+#[allow(clippy::too_many_arguments)]
+pub fn write_project_pages(
+    files: &mut Files,
+    content_dir: &Path,
+    projects: &[Project],
+    related: &[Vec<(usize, f64)>],
+    tax: &Taxonomy,
+    ver: &str,
+    jobs: usize,
+    inc: &Incremental,
+) -> Result<(), BuildError> {
+    let mut seen = BTreeSet::new();
+    for p in projects {
+        if !seen.insert(p.slug()) {
+            return Err(BuildError::config(format!("duplicate project slug `{}` (set `slug` on one of the projects)", p.slug())));
+        }
+    }
+
+    let dir = PathBuf::from("projects");
+    let indices: Vec<usize> = (0..projects.len()).collect();
+    let pages = pool::map(jobs, &indices, |&i| -> Result<(Files, Vec<String>), BuildError> {
+        let p = &projects[i];
+        let page_dir = dir.join(p.slug());
+        // README からコピーする画像と警告はページごとに持つ
+        let mut out = Files::new();
+        let mut warnings = Vec::new();
+        // 本文: content/projects/<slug>.md → ローカルのチェックアウトの README → なし
+        let content = content_dir.join(format!("{}.md", p.slug()));
+        let md = match fs::read_to_string(&content) {
+            Ok(md) => Some(md),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(BuildError::io(&content, e)),
+        };
+        let readme = match md {
+            None if !p.source.is_empty() => import::find_readme(Path::new(&p.source)),
+            _ => None,
+        };
+        let prev = i.checked_sub(1).map(|j| &projects[j]);
+        let next = projects.get(i + 1);
+        let rel: Vec<&Project> = related.get(i).map(|r| r.iter().map(|&(j, _)| &projects[j]).collect()).unwrap_or_default();
+        let page = |body: Option<&str>| subpage(&p.title, "../../", ver, &detail_html(p, body, prev, next, &rel, tax)).into_bytes();
+        let path = page_dir.join("index.html");
+        let bytes = match readme {
+            // README は画像のコピーも伴うので毎回描画する
+            Some(readme) => {
+                let body = readme::render(&readme, &p.github_url, &page_dir, &mut out, &mut warnings)
+                    .map_err(|e| e.context(format!("rendering the page for project `{}`", p.slug())))?;
+                page(Some(&body))
+            }
+            None => {
+                let input = detail_inputs(p, md.as_deref(), prev, next, &rel, tax, ver);
+                inc.get_or_render(&path, input, || page(md.as_deref().map(markdown::to_html).as_deref()))
+            }
+        };
+        out.insert(path, bytes);
+        Ok((out, warnings))
+    });
+    for page in pages {
+        let (out, warnings) = page?;
+        for w in warnings {
+            eprintln!("{w}");
+        }
+        files.extend(out);
+    }
+    Ok(())
+}
+
+/// detail_html() と subpage() が使う項目の入力ハッシュ（ページに項目を足したらここにも足す）
+fn detail_inputs(p: &Project, md: Option<&str>, prev: Option<&Project>, next: Option<&Project>, related: &[&Project], tax: &Taxonomy, ver: &str) -> String {
+    let mut fp = Fingerprint::new();
+    fp.add(ver);
+    add_card_inputs(&mut fp, p);
+    let labels: Vec<&str> = p.tags.iter().map(|t| tax.label(t)).collect();
+    fp.add(&p.role).add(&p.start).add(&p.end).add_list(&p.stack).add_list(&p.gallery).add_list(&labels);
+    fp.add_list(md.as_slice());
+    for q in [prev, next] {
+        fp.add_list(&q.map(|q| vec![q.slug(), q.title.clone()]).unwrap_or_default());
+    }
+    fp.add((related.len() as u64).to_le_bytes());
+    for q in related {
+        add_card_inputs(&mut fp, q);
+    }
+    fp.finish()
+}
+
+fn detail_html(
+    p: &Project,
+    body: Option<&str>,
+    prev: Option<&Project>,
+    next: Option<&Project>,
+    related: &[&Project],
+    tax: &Taxonomy,
+) -> String {
+    let mut s = String::new();
+    s.push_str(r#"<article class="proj-detail">"#);
+    s.push_str(&format!("\n<h3>{}</h3>\n", html_escape(&p.title)));
+    if !p.image_url.is_empty() {
+        s.push_str(&format!(r#"<img class="proj-hero" src="{}" alt="{}">"#, html_attr(&asset_url("../../", &p.image_url)), html_attr(&p.title)));
+        s.push('\n');
+    }
+
+    if !p.description.is_empty() {
+        s.push_str(&format!(r#"<p class="proj-lead">{}</p>"#, html_escape(&p.description)));
+        s.push('\n');
+    }
+
+    // status / role / timeframe / stack / tags
+    let mut meta = String::new();
+    let badges = project_badges_html(p);
+    if !badges.is_empty() {
+        meta.push_str(&format!(r#"<dt>status</dt><dd class="proj-badges-inline">{badges}</dd>"#));
+    }
+    for (k, v) in [("role", p.role.clone()), ("timeframe", p.timeframe())] {
+        if !v.is_empty() {
+            meta.push_str(&format!("<dt>{k}</dt><dd>{}</dd>", html_escape(&v)));
+        }
+    }
+    if !p.stack.is_empty() {
+        let items: String = p.stack.iter().map(|t| format!(r#"<li class="tag">{}</li>"#, html_escape(t))).collect();
+        meta.push_str(&format!(r#"<dt>stack</dt><dd><ul class="stack">{items}</ul></dd>"#));
+    }
+    if !p.tags.is_empty() {
+        let items: String = p
+            .tags
+            .iter()
+            .map(|t| format!(r#"<a class="chip" href="../../tags/{}/">{}</a> "#, url_path(&slugify(t)), html_escape(tax.label(t))))
+            .collect();
+        meta.push_str(&format!("<dt>tags</dt><dd>{}</dd>", items.trim_end()));
+    }
+    if !meta.is_empty() {
+        s.push_str(&format!(r#"<dl class="proj-meta">{meta}</dl>"#));
+        s.push('\n');
+    }
+
+    let mut actions = String::new();
+    if !p.github_url.is_empty() {
+        actions.push_str(&format!(r#"<a class="btn" href="{}" target="_blank" rel="noreferrer">GitHub</a>"#, html_attr(&p.github_url)));
+    }
+    if !p.demo_url.is_empty() {
+        actions.push_str(&format!(r#"<a class="btn ghost" href="{}" target="_blank" rel="noreferrer">Demo</a>"#, html_attr(&p.demo_url)));
+    }
+    if !actions.is_empty() {
+        s.push_str(&format!(r#"<div class="about-actions">{actions}</div>"#));
+        s.push('\n');
+    }
+
+    if let Some(body) = body {
+        s.push_str(&format!("<div class=\"proj-body\">\n{body}</div>\n"));
+    }
+
+    if !p.gallery.is_empty() {
+        s.push_str(r#"<div class="gallery">"#);
+        for (n, src) in p.gallery.iter().enumerate() {
+            s.push_str(&format!(
+                r#"<a href="{src}" target="_blank" rel="noreferrer"><img loading="lazy" src="{src}" alt="{} ({})"></a>"#,
+                html_attr(&p.title),
+                n + 1,
+                src = html_attr(&asset_url("../../", src)),
+            ));
+        }
+        s.push_str("</div>\n");
+    }
+    s.push_str("</article>\n");
+
+    if !related.is_empty() {
+        let cards: String = related.iter().map(|q| project_card_html(q, "../../")).collect();
+        s.push_str(&format!(
+            "<section class=\"proj-related\" aria-label=\"related projects\">\n<h4>related</h4>\n<div class=\"proj-grid\">{cards}</div>\n</section>\n"
+        ));
+    }
+
+    // 前後の Project へのナビ
+    let link = |q: Option<&Project>, rel: &str, arrow: &str| match q {
+        Some(q) => match rel {
+            "prev" => format!(r#"<a rel="prev" href="../{}/">{arrow} {}</a>"#, url_path(&q.slug()), html_escape(&q.title)),
+            _ => format!(r#"<a rel="next" href="../{}/">{} {arrow}</a>"#, url_path(&q.slug()), html_escape(&q.title)),
+        },
+        None => "<span></span>".to_string(),
+    };
+    s.push_str(&format!(
+        r#"<nav class="proj-pager" aria-label="project navigation">{}{}</nav>"#,
+        link(prev, "prev", "←"),
+        link(next, "next", "→"),
+    ));
+    s
+}
@@ -0,0 +1,502 @@
+/*
+ * FileName:        qr
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 15:05:52
+ * LastModified:    2026-10-19 15:05:52
+ * Reference:       8ucchiman.github.io
+ * Description:     QR コードのエンコーダ（バイトモードのみ）と SVG 出力
+ */
+
+
+/// 誤り訂正レベル（復元できる割合: L 7% / M 15% / Q 25% / H 30%）。Site の qr_ecc で選ぶ
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub enum Ecc {
+    L,
+    #[default]
+    M,
+    Q,
+    H,
+}
+
+impl Ecc {
+    fn ordinal(self) -> usize {
+        match self {
+            Ecc::L => 0,
+            Ecc::M => 1,
+            Ecc::Q => 2,
+            Ecc::H => 3,
+        }
+    }
+
+    /// 形式情報に入れる 2 ビット
+    fn format_bits(self) -> u32 {
+        match self {
+            Ecc::L => 1,
+            Ecc::M => 0,
+            Ecc::Q => 3,
+            Ecc::H => 2,
+        }
+    }
+}
+
+/// ブロックあたりの誤り訂正コード語数 [ecc][version]
+const ECC_CODEWORDS_PER_BLOCK: [[u8; 41]; 4] = [
+    [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30, 30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28],
+    [0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24, 28, 26, 24, 20, 30, 24, 28, 28, 26, 30, 28, 30, 30, 30, 30, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+    [0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28, 24, 28, 22, 24, 24, 30, 28, 28, 26, 28, 30, 24, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30],
+];
+
+/// 誤り訂正ブロック数 [ecc][version]
+const NUM_BLOCKS: [[u8; 41]; 4] = [
+    [0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14, 15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25],
+    [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49],
+    [0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8, 8, 10, 12, 16, 12, 17, 16, 18, 21, 20, 23, 23, 25, 27, 29, 34, 34, 35, 38, 40, 43, 45, 48, 51, 53, 56, 59, 62, 65, 68],
+    [0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8, 11, 11, 16, 16, 18, 16, 19, 21, 25, 25, 25, 34, 30, 32, 35, 37, 40, 42, 45, 48, 51, 54, 57, 60, 63, 66, 70, 74, 77, 81],
+];
+
+/// 完成した QR コード。`modules[y][x]` が true なら暗モジュール。
+pub struct QrCode {
+    pub version: usize,
+    pub ecc: Ecc,
+    pub mask: u8,
+    pub size: usize,
+    modules: Vec<Vec<bool>>,
+    /// 機能パターン（ファインダ・タイミングなど）の位置。マスクを掛けない
+    function: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// `data` をバイトモードで符号化する。入る最小の型番を選び、マスクはペナルティが最小のものにする。
+    /// 40 型にも入らなければ None。
+    pub fn encode(data: &[u8], ecc: Ecc) -> Option<QrCode> {
+        Self::encode_with_mask(data, ecc, None)
+    }
+
+    /// マスクを固定する版（`None` なら自動選択）
+    pub fn encode_with_mask(data: &[u8], ecc: Ecc, mask: Option<u8>) -> Option<QrCode> {
+        let version = (1..=40).find(|&v| {
+            let count_bits = if v <= 9 { 8 } else { 16 };
+            4 + count_bits + data.len() * 8 <= num_data_codewords(v, ecc) * 8 && data.len() < 1 << count_bits
+        })?;
+
+        // データのビット列: モード(0100) + 文字数 + データ + 終端 + パディング
+        let mut bits = BitBuffer::default();
+        bits.push(0b0100, 4);
+        bits.push(data.len() as u32, if version <= 9 { 8 } else { 16 });
+        for &b in data {
+            bits.push(b as u32, 8);
+        }
+        let capacity = num_data_codewords(version, ecc) * 8;
+        bits.push(0, (capacity - bits.len()).min(4));
+        bits.push(0, (8 - bits.len() % 8) % 8);
+        for pad in [0xEC, 0x11].iter().cycle() {
+            if bits.len() >= capacity {
+                break;
+            }
+            bits.push(*pad, 8);
+        }
+        let codewords = add_ecc_and_interleave(&bits.to_bytes(), version, ecc);
+
+        let size = version * 4 + 17;
+        let mut qr = QrCode {
+            version,
+            ecc,
+            mask: 0,
+            size,
+            modules: vec![vec![false; size]; size],
+            function: vec![vec![false; size]; size],
+        };
+        qr.draw_function_patterns();
+        qr.draw_codewords(&codewords);
+
+        let mask = match mask {
+            Some(m) => m.min(7),
+            None => (0..8u8)
+                .min_by_key(|&m| {
+                    qr.apply_mask(m);
+                    qr.draw_format_bits(m);
+                    let p = qr.penalty();
+                    qr.apply_mask(m); // XOR なのでもう一度掛ければ元に戻る
+                    p
+                })
+                .unwrap_or(0),
+        };
+        qr.apply_mask(mask);
+        qr.draw_format_bits(mask);
+        qr.mask = mask;
+        Some(qr)
+    }
+
+    /// SVG（周囲に 4 モジュールの余白）。横に続く暗モジュールは 1 つの矩形にまとめる
+    pub fn to_svg(&self, title: &str) -> String {
+        let border = 4;
+        let dim = self.size + border * 2;
+        let mut d = String::new();
+        for y in 0..self.size {
+            let mut x = 0;
+            while x < self.size {
+                if !self.modules[y][x] {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < self.size && self.modules[y][x] {
+                    x += 1;
+                }
+                d.push_str(&format!("M{} {}h{}v1h-{}z", start + border, y + border, x - start, x - start));
+            }
+        }
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {dim} {dim}" shape-rendering="crispEdges" role="img"><title>{}</title><rect width="100%" height="100%" fill="#fff"/><path fill="#000" d="{d}"/></svg>
+"##,
+            crate::html_escape(title)
+        )
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y][x] = dark;
+        self.function[y][x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        let size = self.size;
+        // タイミングパターン
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        // ファインダパターン（分離パターン込み）
+        for (cx, cy) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            for dy in -4i32..=4 {
+                for dx in -4i32..=4 {
+                    let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+                    if (0..size as i32).contains(&x) && (0..size as i32).contains(&y) {
+                        let dist = dx.abs().max(dy.abs());
+                        self.set_function(x as usize, y as usize, dist != 2 && dist != 4);
+                    }
+                }
+            }
+        }
+        // 位置合わせパターン（ファインダと重なる 3 隅以外）
+        let pos = alignment_positions(self.version);
+        let n = pos.len();
+        for (i, &ay) in pos.iter().enumerate() {
+            for (j, &ax) in pos.iter().enumerate() {
+                if (i == 0 && (j == 0 || j == n - 1)) || (i == n - 1 && j == 0) {
+                    continue;
+                }
+                for dy in -2i32..=2 {
+                    for dx in -2i32..=2 {
+                        let dist = dx.abs().max(dy.abs());
+                        self.set_function((ax as i32 + dx) as usize, (ay as i32 + dy) as usize, dist != 1);
+                    }
+                }
+            }
+        }
+        // 形式情報の場所を確保（中身はマスク決定後）
+        self.draw_format_bits(0);
+        // 型番情報（7 型以上）
+        if self.version >= 7 {
+            let mut rem = self.version as u32;
+            for _ in 0..12 {
+                rem = (rem << 1) ^ ((rem >> 11) * 0x1F25);
+            }
+            let bits = (self.version as u32) << 12 | rem;
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 != 0;
+                let a = size - 11 + i % 3;
+                let b = i / 3;
+                self.set_function(a, b, dark);
+                self.set_function(b, a, dark);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u8) {
+        let data = self.ecc.format_bits() << 3 | mask as u32;
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = (data << 10 | rem) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size;
+
+        // 左上
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+        // 右上・左下
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true); // 常に暗
+    }
+
+    /// ジグザグにデータを置く（右下から 2 列ずつ、上下に往復）
+    fn draw_codewords(&mut self, data: &[u8]) {
+        let size = self.size;
+        let mut i = 0;
+        let mut right = size as i32 - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vert in 0..size {
+                for j in 0..2 {
+                    let x = right as usize - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward { size - 1 - vert } else { vert };
+                    if !self.function[y][x] && i < data.len() * 8 {
+                        self.modules[y][x] = (data[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u8) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !self.function[y][x] {
+                    self.modules[y][x] ^= true;
+                }
+            }
+        }
+    }
+
+    /// マスク選択用のペナルティ（JIS X 0510 の 4 規則）
+    fn penalty(&self) -> u32 {
+        let n = self.size;
+        let mut score = 0;
+        let line = |horizontal: bool, i: usize| -> Vec<bool> {
+            (0..n).map(|j| if horizontal { self.modules[i][j] } else { self.modules[j][i] }).collect()
+        };
+        const FINDER_A: [bool; 11] = [true, false, true, true, true, false, true, false, false, false, false];
+        const FINDER_B: [bool; 11] = [false, false, false, false, true, false, true, true, true, false, true];
+        for horizontal in [true, false] {
+            for i in 0..n {
+                let row = line(horizontal, i);
+                // 1) 同色が 5 個以上続く
+                let mut run = 1;
+                for j in 1..=n {
+                    if j < n && row[j] == row[j - 1] {
+                        run += 1;
+                    } else {
+                        if run >= 5 {
+                            score += 3 + (run - 5) as u32;
+                        }
+                        run = 1;
+                    }
+                }
+                // 3) ファインダに似た 1:1:3:1:1 の並び（片側に明 4 個）
+                for w in row.windows(11) {
+                    if w == FINDER_A || w == FINDER_B {
+                        score += 40;
+                    }
+                }
+            }
+        }
+        // 2) 2x2 の同色ブロック
+        for y in 0..n - 1 {
+            for x in 0..n - 1 {
+                let c = self.modules[y][x];
+                if c == self.modules[y][x + 1] && c == self.modules[y + 1][x] && c == self.modules[y + 1][x + 1] {
+                    score += 3;
+                }
+            }
+        }
+        // 4) 暗モジュールの割合が 50% から離れるほど
+        let total = (n * n) as i64;
+        let dark = self.modules.iter().flatten().filter(|&&d| d).count() as i64;
+        let k = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
+        score + k.max(0) as u32 * 10
+    }
+}
+
+#[derive(Default)]
+struct BitBuffer {
+    bits: Vec<bool>,
+}
+
+impl BitBuffer {
+    fn push(&mut self, value: u32, len: usize) {
+        for i in (0..len).rev() {
+            self.bits.push((value >> i) & 1 != 0);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.bits.chunks(8).map(|c| c.iter().fold(0u8, |acc, &b| acc << 1 | b as u8)).collect()
+    }
+}
+
+/// データ領域のモジュール数（機能パターンを除く）
+fn num_raw_data_modules(ver: usize) -> usize {
+    let mut result = (16 * ver + 128) * ver + 64;
+    if ver >= 2 {
+        let num_align = ver / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if ver >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn num_data_codewords(ver: usize, ecc: Ecc) -> usize {
+    let e = ecc.ordinal();
+    num_raw_data_modules(ver) / 8 - ECC_CODEWORDS_PER_BLOCK[e][ver] as usize * NUM_BLOCKS[e][ver] as usize
+}
+
+fn alignment_positions(ver: usize) -> Vec<usize> {
+    if ver == 1 {
+        return Vec::new();
+    }
+    let num_align = ver / 7 + 2;
+    let step = if ver == 32 { 26 } else { (ver * 4 + num_align * 2 + 1) / (num_align * 2 - 2) * 2 };
+    let size = ver * 4 + 17;
+    let mut result: Vec<usize> = (0..num_align - 1).map(|i| size - 7 - i * step).collect();
+    result.push(6);
+    result.reverse();
+    result
+}
+
+/// ブロックに分けて Reed-Solomon の誤り訂正を付け、交互に並べる
+fn add_ecc_and_interleave(data: &[u8], ver: usize, ecc: Ecc) -> Vec<u8> {
+    let e = ecc.ordinal();
+    let num_blocks = NUM_BLOCKS[e][ver] as usize;
+    let block_ecc_len = ECC_CODEWORDS_PER_BLOCK[e][ver] as usize;
+    let raw_codewords = num_raw_data_modules(ver) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_block_len = raw_codewords / num_blocks;
+
+    let divisor = rs_divisor(block_ecc_len);
+    let mut blocks: Vec<Vec<u8>> = Vec::with_capacity(num_blocks);
+    let mut k = 0;
+    for i in 0..num_blocks {
+        let len = short_block_len - block_ecc_len + usize::from(i >= num_short_blocks);
+        let mut block = data[k..k + len].to_vec();
+        k += len;
+        let ecc_bytes = rs_remainder(&block, &divisor);
+        if i < num_short_blocks {
+            block.push(0); // 短いブロックの穴埋め（並べるときに飛ばす）
+        }
+        block.extend(ecc_bytes);
+        blocks.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..blocks[0].len() {
+        for (j, block) in blocks.iter().enumerate() {
+            if i != short_block_len - block_ecc_len || j >= num_short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+/// GF(2^8)（原始多項式 0x11D）上の積
+fn gf_mul(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u32 >> i) & 1) * x as u32;
+    }
+    z as u8
+}
+
+/// 生成多項式の係数（最高次の 1 は省略）
+fn rs_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0u8; degree];
+    result[degree - 1] = 1;
+    let mut root: u8 = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_mul(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_mul(root, 0x02);
+    }
+    result
+}
+
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for &b in data {
+        let factor = b ^ result[0];
+        result.remove(0);
+        result.push(0);
+        for (r, &d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_mul(d, factor);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 左上の形式情報（15 ビット、ビット 0 から）を読む
+    fn read_format(qr: &QrCode) -> u32 {
+        let mut at: Vec<(usize, usize)> = (0..=5).map(|i| (8, i)).collect();
+        at.extend([(8, 7), (8, 8), (7, 8)]);
+        at.extend((9..15).map(|i| (14 - i, 8)));
+        at.iter().enumerate().map(|(i, &(x, y))| (qr.modules[y][x] as u32) << i).sum()
+    }
+
+    /// ISO/IEC 18004 の形式情報の表（BCH 符号 + マスク 101010000010010 済み）
+    #[test]
+    fn format_bits_match_the_spec_table() {
+        let table = [
+            (Ecc::L, 0, 0b111011111000100),
+            (Ecc::M, 0, 0b101010000010010),
+            (Ecc::M, 1, 0b101000100100101),
+            (Ecc::M, 7, 0b100101010100000),
+            (Ecc::Q, 0, 0b011010101011111),
+            (Ecc::H, 0, 0b001011010001001),
+        ];
+        for (ecc, mask, want) in table {
+            let qr = QrCode::encode_with_mask(b"https://example.com/", ecc, Some(mask)).unwrap();
+            assert_eq!((qr.ecc, qr.mask), (ecc, mask));
+            assert_eq!(read_format(&qr), want, "{ecc:?} mask {mask}");
+        }
+    }
+
+    #[test]
+    fn higher_ecc_needs_a_larger_version() {
+        let data = [b'x'; 40];
+        let versions: Vec<usize> = [Ecc::L, Ecc::M, Ecc::Q, Ecc::H].iter().map(|&e| QrCode::encode(&data, e).unwrap().version).collect();
+        assert_eq!(versions, [3, 3, 4, 5]);
+    }
+}
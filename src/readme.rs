@@ -0,0 +1,174 @@
+/*
+ * FileName:        readme
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 17:52:44
+ * LastModified:    2026-10-18 17:52:44
+ * Reference:       8ucchiman.github.io
+ * Description:     ローカルのチェックアウトの README.md を詳細ページの本文にする
+ */
+
+
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{BuildError, IoResultExt},
+    import::github_repo,
+    markdown::{self, Options, UrlKind},
+    percent_decode, url_path, Files,
+};
+
+/// README を HTML にする。相対パスのリンク・画像は次のように書き換える:
+///
+/// - 画像: チェックアウト内にあれば出力の `<page_dir>/files/` にコピーしてそれを参照（`page_dir` は dist 直下から）
+/// - それ以外: `github_url` が GitHub なら `https://github.com/<owner>/<repo>/blob/HEAD/<path>`
+///   （ディレクトリは `tree`、画像は `raw`）
+/// - GitHub でもなくファイルも無ければそのまま（`warnings` に足す）
+///
+/// 先頭の `# タイトル` はページ側のタイトルと重なるので落とす。
+/// 警告は並列に描画しても順番が揺れないよう、出力せずに `warnings` に積む。
+pub fn render(readme: &Path, github_url: &str, page_dir: &Path, files: &mut Files, warnings: &mut Vec<String>) -> Result<String, BuildError> {
+    let src = fs::read_to_string(readme).at(readme)?;
+    let root = readme.parent().unwrap_or(Path::new("."));
+    let gh = github_repo(github_url);
+    let copies: RefCell<Vec<(PathBuf, String)>> = RefCell::new(Vec::new());
+    let warn = RefCell::new(Vec::new());
+
+    let rewrite = |url: &str, kind: UrlKind| -> String {
+        if url.is_empty() || url.starts_with('#') || url.starts_with("//") || has_scheme(url) {
+            return url.to_string();
+        }
+        let split = url.find(['?', '#']).unwrap_or(url.len());
+        let (path, suffix) = url.split_at(split);
+        let Some(rel) = clean_rel_path(&percent_decode(path)) else {
+            warn.borrow_mut().push(format!("warning: {}: link `{url}` points outside the repository", readme.display()));
+            return url.to_string();
+        };
+        let local = root.join(&rel);
+        let copy = |rel: &str| {
+            let mut c = copies.borrow_mut();
+            if !c.iter().any(|(_, r)| r == rel) {
+                c.push((local.clone(), rel.to_string()));
+            }
+            format!("files/{}{suffix}", url_path(rel))
+        };
+        match (&gh, kind) {
+            (_, UrlKind::Image) if local.is_file() => copy(&rel),
+            (Some((owner, repo)), _) => {
+                let mode = match kind {
+                    UrlKind::Image => "raw",
+                    UrlKind::Link if local.is_dir() => "tree",
+                    UrlKind::Link => "blob",
+                };
+                format!("https://github.com/{owner}/{repo}/{mode}/HEAD/{}{suffix}", url_path(&rel))
+            }
+            (None, _) if local.is_file() => copy(&rel),
+            (None, _) => {
+                warn.borrow_mut().push(format!("warning: {}: cannot resolve relative link `{url}` (no GitHub URL and no such file)", readme.display()));
+                url.to_string()
+            }
+        }
+    };
+    let html = markdown::to_html_with(&src, &Options { rewrite_url: Some(&rewrite) });
+    warnings.extend(warn.into_inner());
+
+    for (from, rel) in copies.into_inner() {
+        files.insert(page_dir.join("files").join(&rel), fs::read(&from).at(&from)?);
+    }
+
+    // 先頭の h1 を落とす
+    let html = match html.strip_prefix("<h1") {
+        Some(rest) => match rest.find("</h1>") {
+            Some(end) => rest[end + 5..].trim_start_matches('\n').to_string(),
+            None => html,
+        },
+        None => html,
+    };
+    Ok(html)
+}
+
+fn has_scheme(url: &str) -> bool {
+    url.split_once(':').is_some_and(|(scheme, _)| {
+        !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c))
+    })
+}
+
+/// `./a/../b.png` → `b.png`。リポジトリの外に出るものは None。
+fn clean_rel_path(path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for seg in path.split('/') {
+        match seg {
+            "" | "." => {}
+            ".." => { parts.pop()?; }
+            s => parts.push(s),
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// README.md と docs/（shot.png, guide.md）のあるチェックアウト
+    fn checkout(name: &str, readme: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bucchiman-readme-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(dir.join("docs/shot.png"), b"png").unwrap();
+        fs::write(dir.join("docs/guide.md"), "# guide").unwrap();
+        fs::write(dir.join("README.md"), readme).unwrap();
+        dir
+    }
+
+    fn render_in(dir: &Path, github_url: &str) -> (String, Files, Vec<String>) {
+        let (mut files, mut warnings) = (Files::new(), Vec::new());
+        let html = render(&dir.join("README.md"), github_url, Path::new("projects/p"), &mut files, &mut warnings).unwrap();
+        (html, files, warnings)
+    }
+
+    #[test]
+    fn clean_rel_path_stays_inside_the_repository() {
+        assert_eq!(clean_rel_path("./a/../b.png").as_deref(), Some("b.png"));
+        assert_eq!(clean_rel_path("docs//img/./x.png").as_deref(), Some("docs/img/x.png"));
+        assert_eq!(clean_rel_path("../x.md"), None);
+        assert_eq!(clean_rel_path("a/../../x.md"), None);
+        assert_eq!(clean_rel_path("./"), None);
+    }
+
+    #[test]
+    fn percent_decode_keeps_invalid_escapes() {
+        assert_eq!(percent_decode("my%20shot%E3%81%82.png"), "my shotあ.png");
+        assert_eq!(percent_decode("100%25%zz%4"), "100%%zz%4");
+    }
+
+    #[test]
+    fn relative_links_map_to_github_blob_tree_and_raw() {
+        let dir = checkout("github", "# Title\n\n[guide](docs/guide.md#usage) [docs](./docs) ![shot](docs/my%20shot.png?v=1) ![local](docs/shot.png) [site](https://e.com) [top](#top)\n");
+        let (html, files, warnings) = render_in(&dir, "https://github.com/owner/repo.git");
+        assert_eq!(
+            html,
+            "<p><a href=\"https://github.com/owner/repo/blob/HEAD/docs/guide.md#usage\">guide</a> \
+             <a href=\"https://github.com/owner/repo/tree/HEAD/docs\">docs</a> \
+             <img src=\"https://github.com/owner/repo/raw/HEAD/docs/my%20shot.png?v=1\" alt=\"shot\"> \
+             <img src=\"files/docs/shot.png\" alt=\"local\"> <a href=\"https://e.com\">site</a> <a href=\"#top\">top</a></p>\n"
+        );
+        // 手元にある画像だけページの files/ にコピーする
+        assert_eq!(files.keys().collect::<Vec<_>>(), [Path::new("projects/p/files/docs/shot.png")]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+    }
+
+    #[test]
+    fn unresolvable_links_are_kept_with_a_warning() {
+        let dir = checkout("local", "[up](../other/README.md) [guide](docs/guide.md) [gone](missing.md)\n");
+        let (html, files, warnings) = render_in(&dir, "");
+        assert_eq!(html, "<p><a href=\"../other/README.md\">up</a> <a href=\"files/docs/guide.md\">guide</a> <a href=\"missing.md\">gone</a></p>\n");
+        assert!(files.contains_key(Path::new("projects/p/files/docs/guide.md")));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("`../other/README.md` points outside the repository"), "{}", warnings[0]);
+        assert!(warnings[1].contains("cannot resolve relative link `missing.md`"), "{}", warnings[1]);
+    }
+}
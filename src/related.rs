@@ -0,0 +1,159 @@
+/*
+ * FileName:        related
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 15:48:22
+ * LastModified:    2026-10-18 15:48:22
+ * Reference:       8ucchiman.github.io
+ * Description:     ビルド時に Project 同士の関連度を計算する
+ */
+
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{taxonomy::Taxonomy, Project, Tab};
+
+/// スコアの重み（合計 1.0）
+const W_TAGS: f64 = 0.6;
+const W_TABS: f64 = 0.2;
+const W_TEXT: f64 = 0.2;
+
+/// 説明文の比較で無視する語
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "into", "is", "it", "of", "on", "or",
+    "the", "to", "with", "without", "this", "that", "my", "our", "small", "simple", "minimal",
+];
+
+/// 各 Project について関連度の高い順に最大 `top_n` 件の (index, score) を返す。
+/// スコアは 0.0〜1.0。タグの一致（珍しいタグほど重い）、同じタブ、説明文の類似度から計算する。
+pub fn compute(projects: &[Project], tabs: &[Tab], tax: &Taxonomy, top_n: usize) -> Vec<Vec<(usize, f64)>> {
+    let n = projects.len();
+    let tag_sets: Vec<BTreeSet<&str>> = projects.iter().map(|p| tax.expand(&p.tags).into_iter().collect()).collect();
+    let tab_sets: Vec<BTreeSet<&str>> = projects.iter().map(|p| p.tab_keys(tabs, tax).into_iter().collect()).collect();
+
+    // タグの希少度（IDF）
+    let mut df: BTreeMap<&str, usize> = BTreeMap::new();
+    for set in &tag_sets {
+        for t in set {
+            *df.entry(t).or_default() += 1;
+        }
+    }
+    let idf = |t: &str| (1.0 + n as f64 / df.get(t).copied().unwrap_or(1) as f64).ln();
+    let tag_mass: Vec<f64> = tag_sets.iter().map(|s| s.iter().map(|t| idf(t)).sum()).collect();
+
+    let text = tfidf_vectors(projects);
+
+    let mut out = Vec::with_capacity(n);
+    for a in 0..n {
+        let mut scored: Vec<(usize, f64)> = (0..n)
+            .filter(|&b| b != a)
+            .map(|b| {
+                let shared: f64 = tag_sets[a].intersection(&tag_sets[b]).map(|t| idf(t)).sum();
+                let tags = if shared > 0.0 { shared / (tag_mass[a] * tag_mass[b]).sqrt() } else { 0.0 };
+                let tabs = jaccard(&tab_sets[a], &tab_sets[b]);
+                let txt = cosine(&text[a], &text[b]);
+                (b, W_TAGS * tags + W_TABS * tabs + W_TEXT * txt)
+            })
+            .filter(|&(_, s)| s > 0.0)
+            .collect();
+        // 同点なら元の並び順（featured → weight → 日付）を優先
+        scored.sort_by(|x, y| y.1.total_cmp(&x.1).then(x.0.cmp(&y.0)));
+        scored.truncate(top_n);
+        out.push(scored);
+    }
+    out
+}
+
+fn jaccard(a: &BTreeSet<&str>, b: &BTreeSet<&str>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 { 0.0 } else { a.intersection(b).count() as f64 / union as f64 }
+}
+
+fn tokens(s: &str) -> Vec<String> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| w.chars().count() > 1 && !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// タイトル + 説明文の TF-IDF ベクトル
+fn tfidf_vectors(projects: &[Project]) -> Vec<BTreeMap<String, f64>> {
+    let docs: Vec<Vec<String>> = projects.iter().map(|p| tokens(&format!("{} {}", p.title, p.description))).collect();
+    let mut df: BTreeMap<&str, usize> = BTreeMap::new();
+    for d in &docs {
+        for w in d.iter().collect::<BTreeSet<_>>() {
+            *df.entry(w.as_str()).or_default() += 1;
+        }
+    }
+    let n = docs.len() as f64;
+    docs.iter()
+        .map(|d| {
+            let mut tf: BTreeMap<String, f64> = BTreeMap::new();
+            for w in d {
+                *tf.entry(w.clone()).or_default() += 1.0;
+            }
+            for (w, v) in tf.iter_mut() {
+                *v *= (n / df[w.as_str()] as f64).ln();
+            }
+            tf
+        })
+        .collect()
+}
+
+fn cosine(a: &BTreeMap<String, f64>, b: &BTreeMap<String, f64>) -> f64 {
+    let dot: f64 = a.iter().filter_map(|(k, x)| b.get(k).map(|y| x * y)).sum();
+    let na: f64 = a.values().map(|x| x * x).sum::<f64>().sqrt();
+    let nb: f64 = b.values().map(|x| x * x).sum::<f64>().sqrt();
+    if na == 0.0 || nb == 0.0 { 0.0 } else { dot / (na * nb) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(title: &str, tags: &[&str]) -> Project {
+        Project { title: title.into(), tags: tags.iter().map(|t| t.to_string()).collect(), ..Default::default() }
+    }
+
+    #[test]
+    fn rare_shared_tags_weigh_more_and_ties_keep_the_order() {
+        let projects = [
+            project("Alpha", &["common", "rare"]),
+            project("Bravo", &["common"]),
+            project("Charlie", &["rare"]),
+            project("Delta", &["common"]),
+            project("Echo", &["common"]),
+            project("Foxtrot", &["other"]),
+        ];
+        let related = compute(&projects, &[], &Taxonomy::default(), 3);
+        let order: Vec<usize> = related[0].iter().map(|&(i, _)| i).collect();
+        assert_eq!(order, [2, 1, 3]);
+        assert!(related[0][0].1 > related[0][1].1 && related[0][1].1 == related[0][2].1);
+        assert!(related[0].iter().all(|&(_, s)| s > 0.0 && s <= 1.0));
+        // 何も共有しないものは出さない
+        assert!(related[5].is_empty());
+        assert_eq!(related.len(), projects.len());
+    }
+
+    #[test]
+    fn parent_tags_tabs_and_descriptions_count() {
+        let Ok(tax) = Taxonomy::from_toml("[3d]\n\n[opengl]\nparent = \"3d\"\n") else { panic!("taxonomy") };
+        let tabs = [Tab { key: "graphics".into(), ..Default::default() }];
+        let mut projects = [
+            project("Alpha", &["opengl"]),
+            project("Bravo", &["3d"]),
+            project("Charlie", &[]),
+            project("Delta", &[]),
+        ];
+        projects[2].tabs = vec!["graphics".into()];
+        projects[3].tabs = vec!["graphics".into()];
+        projects[2].description = "Realtime shader toy".into();
+        projects[3].description = "A realtime shader playground".into();
+        let related = compute(&projects, &tabs, &tax, 5);
+        // opengl の親の 3d で繋がる
+        assert_eq!(related[0].iter().map(|&(i, _)| i).collect::<Vec<_>>(), [1]);
+        // 同じタブ + 説明文の共通語
+        let (i, s) = related[2][0];
+        assert_eq!(i, 3);
+        assert!(s > W_TABS && s < W_TABS + W_TEXT, "{s}");
+    }
+}
@@ -0,0 +1,212 @@
+/*
+ * FileName:        remote
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 09:12:40
+ * LastModified:    2026-10-19 09:12:40
+ * Reference:       8ucchiman.github.io
+ * Description:     リモート画像を cache/remote/ のローカルコピーに差し替える
+ */
+
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{BuildError, IoResultExt},
+    pool, Files,
+};
+
+/// dist 内の置き場所
+pub const OUT_DIR: &str = "assets/remote";
+
+/// リモート URL → `assets/remote/<file>` の対応表。
+///
+/// キャッシュは別途 `cache/remote/` に用意しておく。ファイル名は URL（そのままの文字列）の
+/// SHA-256 の 16 進 64 文字で、拡張子は任意:
+///
+/// ```sh
+/// url=https://picsum.photos/seed/dsp/800/600
+/// curl -sfL "$url" -o "cache/remote/$(printf %s "$url" | sha256sum | cut -c1-64).jpg"
+/// ```
+#[derive(Default)]
+pub struct Localizer {
+    /// URL → (キャッシュのファイル, dist からの相対パス)
+    found: BTreeMap<String, (PathBuf, String)>,
+    /// キャッシュに無かった URL（元の URL のまま残る）
+    pub missing: Vec<String>,
+}
+
+pub fn is_remote(url: &str) -> bool {
+    let u = url.trim_start().to_ascii_lowercase();
+    u.starts_with("http://") || u.starts_with("https://") || u.starts_with("//")
+}
+
+impl Localizer {
+    /// `urls` のうちリモートのものを `cache_dir` から探す。ディレクトリが無ければ全部 missing。
+    pub fn new<'a>(cache_dir: &Path, urls: impl IntoIterator<Item = &'a str>) -> Result<Localizer, BuildError> {
+        // ハッシュ（拡張子を除いたファイル名）→ ファイル
+        let mut files: BTreeMap<String, PathBuf> = BTreeMap::new();
+        match fs::read_dir(cache_dir) {
+            Ok(rd) => {
+                // read_dir の順序は OS 次第なので、同じハッシュのファイルが複数あってもパス順で決まるようにする
+                let mut paths: Vec<PathBuf> = rd.map(|e| e.map(|e| e.path())).collect::<io::Result<_>>().at(cache_dir)?;
+                paths.sort();
+                for path in paths {
+                    if let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+                        && path.is_file()
+                    {
+                        files.entry(stem.to_ascii_lowercase()).or_insert(path);
+                    }
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(BuildError::io(cache_dir, e)),
+        }
+
+        let mut l = Localizer::default();
+        for url in urls {
+            if !is_remote(url) || l.found.contains_key(url) || l.missing.iter().any(|m| m == url) {
+                continue;
+            }
+            match files.get(&key(url)) {
+                Some(path) => {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    l.found.insert(url.to_string(), (path.clone(), format!("{OUT_DIR}/{name}")));
+                }
+                None => l.missing.push(url.to_string()),
+            }
+        }
+        Ok(l)
+    }
+
+    /// ローカルコピーがあればその相対パス、無ければ元の URL
+    pub fn get<'a>(&'a self, url: &'a str) -> &'a str {
+        self.found.get(url).map_or(url, |(_, local)| local.as_str())
+    }
+
+    /// 見つかったキャッシュを出力の `assets/remote/` に加える（最大 `jobs` 並列で読む）
+    pub fn write(&self, files: &mut Files, jobs: usize) -> Result<(), BuildError> {
+        let found: Vec<&(PathBuf, String)> = self.found.values().collect();
+        let read = pool::try_map(jobs, &found, |(from, local)| Ok((PathBuf::from(local), fs::read(from).at(from)?)))?;
+        files.extend(read);
+        Ok(())
+    }
+}
+
+/// キャッシュのキー（URL の SHA-256、小文字 16 進）
+pub fn key(url: &str) -> String {
+    sha256(url.as_bytes()).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+        0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+        0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+        0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+        0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+        0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+        0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+    ];
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+
+    let mut msg = data.to_vec();
+    msg.push(0x80);
+    while msg.len() % 64 != 56 {
+        msg.push(0);
+    }
+    msg.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in msg.chunks_exact(64) {
+        let mut w = [0u32; 64];
+        for (i, c) in block.chunks_exact(4).enumerate() {
+            w[i] = u32::from_be_bytes([c[0], c[1], c[2], c[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (x, v) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+            *x = x.wrapping_add(v);
+        }
+    }
+
+    let mut out = [0u8; 32];
+    for (i, v) in h.iter().enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&v.to_be_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(h: &[u8]) -> String {
+        h.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn sha256_matches_fips_vectors() {
+        assert_eq!(hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(hex(&sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        // 448 ビットのメッセージ（パディングが次のブロックにはみ出す）
+        assert_eq!(
+            hex(&sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(key("abc"), hex(&sha256(b"abc")));
+    }
+
+    #[test]
+    fn localizer_finds_cached_copies_by_url_hash() {
+        let dir = std::env::temp_dir().join(format!("bucchiman-remote-cache-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let (a, b) = ("https://picsum.photos/seed/a/800/600", "https://picsum.photos/seed/b/800/600");
+        // 同じハッシュのファイルが 2 つあればパス順で先のもの
+        fs::write(dir.join(format!("{}.png", key(a))), "png").unwrap();
+        fs::write(dir.join(format!("{}.jpg", key(a))), "jpg").unwrap();
+
+        let l = Localizer::new(&dir, [a, b, a, "assets/local.png"]).unwrap();
+        let local = format!("{OUT_DIR}/{}.jpg", key(a));
+        assert_eq!(l.get(a), local);
+        assert_eq!(l.get(b), b);
+        assert_eq!(l.missing, [b]);
+        assert_eq!(l.get("assets/local.png"), "assets/local.png");
+
+        let mut files = Files::new();
+        l.write(&mut files, 2).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[Path::new(&local)], b"jpg");
+
+        // キャッシュのディレクトリが無ければ全部 missing
+        let l = Localizer::new(&dir.join("none"), [a]).unwrap();
+        assert_eq!(l.missing, [a]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,244 @@
+/*
+ * FileName:        sections
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 10:02:17
+ * LastModified:    2026-10-19 10:02:17
+ * Reference:       8ucchiman.github.io
+ * Description:     トップページのセクション構成（並び・有効/無効・種類）とナビ
+ */
+
+
+use std::collections::BTreeSet;
+
+use crate::{error::BuildError, html_attr, html_escape, markdown, Project};
+
+/// トップページの 1 セクション。`id` がアンカー（`#id`）とナビの `data-target` になる。
+#[derive(Clone)]
+pub struct Section {
+    pub id: String,
+    /// ナビのボタンに出す文字列
+    pub label: String,
+    pub kind: SectionKind,
+    pub enabled: bool,
+}
+
+#[derive(Clone)]
+pub enum SectionKind {
+    /// 全画面プレビュー（タブのメディア）
+    Hero,
+    About,
+    /// タグで絞り込める Project 一覧
+    Projects,
+    Posts(Vec<Entry>),
+    /// Project の期間（start / end）から作る年表
+    Timeline,
+    Publications(Vec<Entry>),
+    Contact,
+    /// そのまま埋め込む HTML
+    Html(String),
+    /// Markdown を HTML にして埋め込む
+    Markdown(String),
+}
+
+impl SectionKind {
+    fn name(&self) -> &'static str {
+        match self {
+            SectionKind::Hero => "hero",
+            SectionKind::About => "about",
+            SectionKind::Projects => "projects",
+            SectionKind::Posts(_) => "posts",
+            SectionKind::Timeline => "timeline",
+            SectionKind::Publications(_) => "publications",
+            SectionKind::Contact => "contact",
+            SectionKind::Html(_) => "html",
+            SectionKind::Markdown(_) => "markdown",
+        }
+    }
+}
+
+/// Posts / Publications の 1 行
+#[derive(Clone, Default)]
+pub struct Entry {
+    pub title: String,
+    pub url: String,
+    /// "YYYY[-MM[-DD]]"
+    pub date: String,
+    /// 掲載先・一言など
+    pub note: String,
+}
+
+/// id の重複・不正な文字、同じページに 1 つしか置けない種類の重複を調べる
+pub fn validate(sections: &[Section]) -> Result<(), BuildError> {
+    let mut ids = BTreeSet::new();
+    let mut singletons = BTreeSet::new();
+    for s in sections {
+        if s.id.is_empty() || !s.id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(BuildError::config(format!("section id `{}` must be non-empty [A-Za-z0-9_-]", s.id)));
+        }
+        if !ids.insert(s.id.as_str()) {
+            return Err(BuildError::config(format!("duplicate section id `{}`", s.id)));
+        }
+        // JS が固定の要素 id（#media, #proj-grid）を探すので 1 つまで
+        let single = matches!(s.kind, SectionKind::Hero | SectionKind::About | SectionKind::Projects | SectionKind::Contact);
+        if s.enabled && single && !singletons.insert(s.kind.name()) {
+            return Err(BuildError::config(format!("section `{}`: only one enabled `{}` section is allowed", s.id, s.kind.name())));
+        }
+    }
+    Ok(())
+}
+
+/// 生成済みのページで、ナビの `data-target="#x"` に対応する `id="x"` があるか調べる
+pub fn check_targets(html: &str) -> Result<(), BuildError> {
+    let ids: BTreeSet<&str> = attr_values(html, "id").into_iter().collect();
+    for target in attr_values(html, "data-target") {
+        let id = target.strip_prefix('#').unwrap_or(target);
+        if !ids.contains(id) {
+            return Err(BuildError::Template { page: "index.html".into(), message: format!("nav target `{target}` has no matching element") });
+        }
+    }
+    Ok(())
+}
+
+fn attr_values<'h>(html: &'h str, name: &str) -> Vec<&'h str> {
+    let pat = format!(" {name}=\"");
+    html.match_indices(&pat)
+        .filter_map(|(at, _)| {
+            let rest = &html[at + pat.len()..];
+            rest.find('"').map(|end| &rest[..end])
+        })
+        .collect()
+}
+
+/// スクロール時に出るタブ式ナビ（有効なセクションだけ、設定の順に）
+pub fn nav_html(sections: &[Section]) -> String {
+    let buttons: String = sections
+        .iter()
+        .filter(|s| s.enabled)
+        .map(|s| {
+            format!(
+                "\n  <button data-target=\"#{id}\" class=\"tablink\" aria-label=\"Go to {label}\">{text}</button>",
+                id = html_attr(&s.id),
+                label = html_attr(&s.label),
+                text = html_escape(&s.label),
+            )
+        })
+        .collect();
+    format!(r#"<nav class="sticky-tabs" id="stickyTabs" role="navigation" aria-label="section tabs">{buttons}
+</nav>"#)
+}
+
+/// hero / about / projects / contact 以外の汎用セクション
+pub fn generic_html(s: &Section, projects: &[Project]) -> String {
+    let body = match &s.kind {
+        SectionKind::Posts(entries) | SectionKind::Publications(entries) => entries_html(entries),
+        SectionKind::Timeline => timeline_html(projects),
+        SectionKind::Html(html) => html.clone(),
+        SectionKind::Markdown(src) => markdown::to_html(src),
+        SectionKind::Hero | SectionKind::About | SectionKind::Projects | SectionKind::Contact => String::new(),
+    };
+    format!(
+        r#"<section class="section section-{kind}" id="{id}" aria-label="{label}">
+  <div class="container">
+    <h3>{title}</h3>
+    {body}
+  </div>
+</section>"#,
+        kind = s.kind.name(),
+        id = html_attr(&s.id),
+        label = html_attr(&s.label),
+        title = html_escape(&s.label),
+    )
+}
+
+fn entries_html(entries: &[Entry]) -> String {
+    if entries.is_empty() {
+        return r#"<p class="desc">Nothing here yet.</p>"#.to_string();
+    }
+    let mut entries: Vec<&Entry> = entries.iter().collect();
+    entries.sort_by(|a, b| b.date.cmp(&a.date));
+    let items: String = entries
+        .iter()
+        .map(|e| {
+            let title = if e.url.is_empty() {
+                html_escape(&e.title)
+            } else {
+                format!(r#"<a href="{}" target="_blank" rel="noreferrer">{}</a>"#, html_attr(&e.url), html_escape(&e.title))
+            };
+            let note = if e.note.is_empty() { String::new() } else { format!(r#" <span class="entry-note">{}</span>"#, html_escape(&e.note)) };
+            format!(r#"<li><time class="entry-date">{}</time> {title}{note}</li>"#, html_escape(&e.date))
+        })
+        .collect();
+    format!(r#"<ul class="entry-list">{items}</ul>"#)
+}
+
+/// 期間のある Project を新しい順に並べる
+fn timeline_html(projects: &[Project]) -> String {
+    let mut dated: Vec<&Project> = projects.iter().filter(|p| !p.start.is_empty() || !p.end.is_empty()).collect();
+    dated.sort_by(|a, b| b.sort_date().cmp(a.sort_date()).then_with(|| b.start.cmp(&a.start)));
+    if dated.is_empty() {
+        return r#"<p class="desc">Nothing here yet.</p>"#.to_string();
+    }
+    let items: String = dated
+        .iter()
+        .map(|p| {
+            format!(
+                r#"<li><time class="entry-date">{}</time> <a href="{}">{}</a></li>"#,
+                html_escape(&p.timeframe()),
+                html_attr(&p.url()),
+                html_escape(&p.title),
+            )
+        })
+        .collect();
+    format!(r#"<ol class="timeline">{items}</ol>"#)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(id: &str, kind: SectionKind, enabled: bool) -> Section {
+        Section { id: id.into(), label: id.to_uppercase(), kind, enabled }
+    }
+
+    fn error(sections: &[Section]) -> String {
+        match validate(sections) {
+            Err(e @ BuildError::Config { .. }) => e.to_string(),
+            Err(e) => panic!("not a config error: {e}"),
+            Ok(()) => panic!("accepted"),
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_and_duplicate_ids() {
+        let about = section("about", SectionKind::About, true);
+        assert!(validate(&[about.clone(), section("posts", SectionKind::Posts(Vec::new()), true)]).is_ok());
+        assert!(error(&[section("a b", SectionKind::Timeline, true)]).contains("section id `a b`"));
+        assert!(error(&[section("", SectionKind::Timeline, true)]).contains("section id ``"));
+        // 同じ id は無効なものでも重複（ナビの data-target がぶつかる）
+        let dup = section("about", SectionKind::Markdown(String::new()), false);
+        assert!(error(&[about.clone(), dup]).contains("duplicate section id `about`"));
+        // 1 つしか置けない種類は、有効なものが 2 つあるときだけ
+        assert!(validate(&[about.clone(), section("me", SectionKind::About, false)]).is_ok());
+        assert!(error(&[about, section("me", SectionKind::About, true)]).contains("only one enabled `about` section"));
+    }
+
+    #[test]
+    fn check_targets_rejects_targets_without_an_element() {
+        let sections = [section("about", SectionKind::About, true), section("posts", SectionKind::Posts(Vec::new()), true)];
+        let nav = nav_html(&sections);
+        assert!(check_targets(&format!("{nav}<section id=\"about\"></section><section id=\"posts\"></section>")).is_ok());
+        let Err(e) = check_targets(&format!("{nav}<section id=\"about\"></section>")) else { panic!("accepted") };
+        assert!(matches!(e, BuildError::Template { .. }) && e.to_string().contains("`#posts`"), "{e}");
+    }
+
+    #[test]
+    fn nav_leaves_out_disabled_sections() {
+        let nav = nav_html(&[
+            section("about", SectionKind::About, true),
+            section("timeline", SectionKind::Timeline, false),
+            section("contact", SectionKind::Contact, true),
+        ]);
+        assert_eq!(attr_values(&nav, "data-target"), ["#about", "#contact"]);
+        assert!(!nav.contains("TIMELINE"));
+    }
+}
@@ -0,0 +1,380 @@
+/*
+ * FileName:        site
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 17:05:42
+ * LastModified:    2026-10-19 17:05:42
+ * Reference:       8ucchiman.github.io
+ * Description:     サイトの設定（Site）と、それを dist に描画する SiteBuilder
+ */
+
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    about::{self, About},
+    cache::{self, BuildCache, BuildReport, Incremental},
+    contacts::{self, Contact},
+    context::BuildContext,
+    error::{BuildError, IoResultExt},
+    icons::Icons,
+    import, index_page, lint, pool, projects,
+    qr::{Ecc, QrCode},
+    related, remote,
+    sections::{self, Section},
+    tags,
+    taxonomy::Taxonomy,
+    vcard, json_str, Files, IndexInputs, Project, Tab, APP_JS, STYLE_CSS,
+};
+
+/// サイトの中身。入力ファイル（taxonomy.toml, content/, assets/ など）は BuildContext の root から読む。
+#[derive(Clone, Default)]
+pub struct Site {
+    /// ヒーローのメディア候補（video 優先 → gif）と Project の分類
+    pub tabs: Vec<Tab>,
+    /// トップページのセクション（この順に並び、ナビもこの順）
+    pub sections: Vec<Section>,
+    /// 連絡先。kind がアイコン名になり、About のボタンからも kind で参照する
+    pub contacts: Vec<Contact>,
+    pub about: About,
+    pub projects: Vec<Project>,
+    /// 隣にチェックアウトしたリポジトリ（Cargo.toml / README.md、root からの相対パス）から Project を作る。
+    /// slug（= package.name）か github_url が一致する `projects` は上書き設定として使われる
+    pub projects_from: Vec<PathBuf>,
+    /// 公開 URL（vCard と QR コードに使う）
+    pub site_url: String,
+    /// contact セクションに vCard の QR コードを出し、contact.vcf を書くか。
+    /// vCard と QR コードにはメールアドレスが（連絡先の obfuscate によらず）そのまま入る
+    pub contact_qr: bool,
+    /// QR コードの誤り訂正レベル（高いほど汚れに強いが、コードが大きくなる）
+    pub qr_ecc: Ecc,
+    /// スプライトではなくページに直接埋め込むアイコン
+    pub inline_icons: Vec<String>,
+    /// ビルドしたコミット・日時をページ下部に出し、/build.json に書く
+    pub build_info: bool,
+    /// 出力先にあっても消さないパス（`CNAME`、`assets/fonts` など。ディレクトリなら中身ごと残す）
+    pub keep: Vec<String>,
+}
+
+/// Site を描画する。
+///
+/// ```ignore
+/// let files = SiteBuilder::new(&site).render()?;
+/// let report = SiteBuilder::new(&site).context(BuildContext::from_env(root)?).deny_warnings(true).jobs(4).write_to(&root.join("dist"))?;
+/// ```
+pub struct SiteBuilder<'s> {
+    site: &'s Site,
+    ctx: BuildContext,
+    deny_warnings: bool,
+    localize_images: bool,
+    jobs: usize,
+    dry_run: bool,
+}
+
+impl<'s> SiteBuilder<'s> {
+    /// 既定の BuildContext（カレントディレクトリ・バージョン "dev"）で、使える CPU の数だけ並列に描画する
+    pub fn new(site: &'s Site) -> SiteBuilder<'s> {
+        SiteBuilder { site, ctx: BuildContext::default(), deny_warnings: false, localize_images: false, jobs: pool::default_jobs(), dry_run: false }
+    }
+
+    pub fn context(mut self, ctx: BuildContext) -> Self {
+        self.ctx = ctx;
+        self
+    }
+
+    /// lint の警告があればエラーにする（CI 用）
+    pub fn deny_warnings(mut self, yes: bool) -> Self {
+        self.deny_warnings = yes;
+        self
+    }
+
+    /// リモート画像を cache/remote/ のコピーに差し替える（オフラインでビルドできる）
+    pub fn localize_images(mut self, yes: bool) -> Self {
+        self.localize_images = yes;
+        self
+    }
+
+    /// ページ・アセットを処理するスレッドの数（1 なら逐次）。並列でも出力・警告・エラーの順番は変わらない
+    pub fn jobs(mut self, n: usize) -> Self {
+        self.jobs = n.max(1);
+        self
+    }
+
+    /// write_to() で何も書かず・消さずに、書くもの・消すものの一覧だけ返す
+    pub fn dry_run(mut self, yes: bool) -> Self {
+        self.dry_run = yes;
+        self
+    }
+
+    /// 全ページ・アセットをメモリ上に描画する。警告は stderr に出す。
+    pub fn render(&self) -> Result<Files, BuildError> {
+        self.render_with(&Incremental::none())
+    }
+
+    /// render() の本体。`inc` に前回の記録があれば、入力が変わっていないページはそれを使い回す
+    fn render_with(&self, inc: &Incremental) -> Result<Files, BuildError> {
+        let site = self.site;
+        let ctx = &self.ctx;
+        let ver = ctx.version.as_str();
+        sections::validate(&site.sections)?;
+        about::validate(&site.about, &site.contacts)?;
+        contacts::validate(&site.contacts)?;
+        validate_site_url(&site.site_url)?;
+
+        // 取り込み元・チェックアウトのパスはここで root 基準にしておく
+        let dirs: Vec<PathBuf> = site.projects_from.iter().map(|d| ctx.path(d)).collect();
+        let imported = import::load_all(&dirs)?;
+        let config = site.projects.iter().map(|p| match p.source.as_str() {
+            "" => p.clone(),
+            src => Project { source: ctx.path(src).to_string_lossy().into_owned(), ..p.clone() },
+        });
+        let mut projects = import::merge(config.collect(), imported.iter().map(import::to_project).collect());
+
+        // タグの正規化（taxonomy.toml の正規名・別名に寄せる。未知のタグは警告）
+        let mut taxonomy = Taxonomy::load(&ctx.path("taxonomy.toml"))?;
+        let unknown_tags = taxonomy.register_unknown(&projects);
+        for p in &mut projects {
+            p.tags = taxonomy.canonical_tags(&p.tags);
+        }
+        projects.sort_by(Project::display_order);
+
+        // コンテンツの lint（プレースホルダ・重複・空のタブなど）
+        let mut warnings: Vec<lint::Warning> =
+            unknown_tags.into_iter().map(|message| lint::Warning { code: "unknown-tag", message }).collect();
+        warnings.extend(lint::check(&site.tabs, &projects, &taxonomy));
+
+        // リモート画像のローカル化（キャッシュに無いものは元の URL のまま警告）
+        let remote = if self.localize_images {
+            let urls = projects.iter().flat_map(|p| std::iter::once(p.image_url.as_str()).chain(p.gallery.iter().map(String::as_str)))
+                .chain(site.tabs.iter().flat_map(|t| [t.gif_url.as_str(), t.video_url.as_str()]));
+            remote::Localizer::new(&ctx.path("cache/remote"), urls)?
+        } else {
+            remote::Localizer::default()
+        };
+        for url in &remote.missing {
+            warnings.push(lint::Warning {
+                code: "remote-not-cached",
+                message: format!("`{url}` is not in cache/remote/ (expected cache/remote/{}.<ext>)", remote::key(url)),
+            });
+        }
+        for w in &warnings {
+            eprintln!("{w}");
+        }
+        if self.deny_warnings && !warnings.is_empty() {
+            return Err(BuildError::Lint { count: warnings.len() });
+        }
+        for p in &mut projects {
+            p.image_url = remote.get(&p.image_url).to_string();
+            p.gallery = p.gallery.iter().map(|u| remote.get(u).to_string()).collect();
+        }
+        let tabs: Vec<Tab> = site
+            .tabs
+            .iter()
+            .map(|t| Tab { gif_url: remote.get(&t.gif_url).to_string(), video_url: remote.get(&t.video_url).to_string(), ..t.clone() })
+            .collect();
+
+        // 関連 Project（詳細ページと JSON に載せる）
+        let related = related::compute(&projects, &tabs, &taxonomy, 3);
+
+        let mut files = Files::new();
+        files.insert(PathBuf::from(".nojekyll"), Vec::new());
+        remote.write(&mut files, self.jobs)?;
+        files.insert(PathBuf::from("assets/style.css"), STYLE_CSS.into());
+        files.insert(PathBuf::from("assets/app.js"), APP_JS.into());
+
+        let has_portrait = about::copy_portrait(&site.about, &ctx.root, &mut files)?;
+        // 連絡先のアイコン（組み込み + assets/icons/*.svg）。通常は assets/icons.svg のスプライトを参照し、
+        // inline_icons に挙げたものだけページに直接埋め込む
+        let inline: Vec<&str> = site.inline_icons.iter().map(String::as_str).collect();
+        let icons = Icons::load(&ctx.path("assets/icons"))?.with_inline(&inline);
+        if let Some(name) = inline.iter().find(|n| !icons.contains(n)) {
+            return Err(BuildError::MissingAsset { path: ctx.path(format!("assets/icons/{name}.svg")), referenced_by: "inline_icons".into() });
+        }
+
+        // 公開 URL の QR コード（assets/qr-site.svg）と、contact_qr なら vCard とその QR コード（contact.vcf, assets/qr-contact.svg）。
+        // vCard にはメールアドレスが難読化せずに入るので、contact_qr が false なら出力に置かない
+        let mut qr = vec![("assets/qr-site.svg", site.site_url.clone(), "QR code: site URL")];
+        if site.contact_qr {
+            let portrait = has_portrait.then(|| files[Path::new(&site.about.portrait)].as_slice());
+            let vcf = vcard::render(&site.about, &site.contacts, &site.site_url, portrait);
+            // 公開 URL が無いと写真は data: URI になり QR コードに入りきらないので、QR 用には写真を除く
+            let qr_vcf = if site.site_url.is_empty() { vcard::render(&site.about, &site.contacts, &site.site_url, None) } else { vcf.clone() };
+            qr.push(("assets/qr-contact.svg", qr_vcf, "QR code: contact card"));
+            files.insert(PathBuf::from("contact.vcf"), vcf.into_bytes());
+        }
+        let svgs = pool::map(self.jobs, &qr, |(_, data, title)| QrCode::encode(data.as_bytes(), site.qr_ecc).map(|code| code.to_svg(title)));
+        for ((file, data, _), svg) in qr.iter().zip(svgs) {
+            match svg {
+                Some(svg) => {
+                    files.insert(PathBuf::from(file), svg.into_bytes());
+                }
+                None => eprintln!("warning: {file}: data too long for a QR code ({} bytes)", data.len()),
+            }
+        }
+
+        // ビルド情報（build.json とページ下部の 1 行）
+        let build_info = if site.build_info {
+            let date = ctx.timestamp();
+            let commit = ctx.git.as_ref().map_or("null".to_string(), |g| json_str(&g.commit));
+            let dirty = ctx.git.as_ref().is_some_and(|g| g.dirty);
+            let json = format!(
+                "{{\n  \"version\": {},\n  \"commit\": {commit},\n  \"dirty\": {dirty},\n  \"date\": {}\n}}\n",
+                json_str(ver),
+                json_str(&date),
+            );
+            files.insert(PathBuf::from("build.json"), json.into_bytes());
+            let rev = match &ctx.git {
+                Some(g) => format!("{}{}", g.short(), if g.dirty { "-dirty" } else { "" }),
+                None => ver.to_string(),
+            };
+            format!("build {rev} · {}", &date[..10])
+        } else {
+            String::new()
+        };
+
+        let index = index_page(site, &IndexInputs {
+            tabs: &tabs,
+            projects: &projects,
+            related: &related,
+            taxonomy: &taxonomy,
+            icons: &icons,
+            ver,
+            has_portrait,
+            year: ctx.year(),
+            build_info: &build_info,
+        });
+        sections::check_targets(&index)?;
+        files.insert(PathBuf::from("index.html"), index.into_bytes());
+        icons.write_sprite(&mut files);
+
+        // タグごとの静的ページ（tags/<tag>/index.html + tags/index.html）
+        tags::write_tag_pages(&mut files, &projects, &taxonomy, ver, self.jobs, inc)?;
+
+        // Project ごとの詳細ページ（projects/<slug>/index.html）
+        projects::write_project_pages(&mut files, &ctx.path("content/projects"), &projects, &related, &taxonomy, ver, self.jobs, inc)?;
+        Ok(files)
+    }
+
+    /// 描画して `out` 以下に書き出す。<root>/.build-cache に前回 `out` に書いた記録があれば、
+    /// 入力が変わっていないページは描画せずに使い回し、中身が変わったファイルだけを書く。
+    /// 出力先にあって今回出していないファイルは（`keep` に当たるものを除いて）消す。
+    /// dry_run なら何も書かず・消さずに、書くもの・消すものを報告だけする
+    pub fn write_to(&self, out: &Path) -> Result<BuildReport, BuildError> {
+        // --out に root（やその親）を渡されたときに入力まで消さないよう、何か書く前に止める
+        if !self.dry_run
+            && let (Ok(root), Ok(dir)) = (self.ctx.root.canonicalize(), out.canonicalize())
+            && root.starts_with(&dir)
+        {
+            return Err(BuildError::config(format!(
+                "refusing to write to `{}`: it contains the site root and stale outputs would be removed from it (use --dry-run to list them)",
+                out.display(),
+            )));
+        }
+        let manifest = self.ctx.path(cache::FILE);
+        let prev = BuildCache::load(&manifest, out);
+        let inc = Incremental::from(&prev, out);
+        let files = self.render_with(&inc)?;
+        let (inputs, reused) = inc.finish();
+        let stale = stale_files(&files, out, &self.site.keep)?;
+        let written = if self.dry_run {
+            changed_files(&files, out, self.jobs)
+        } else {
+            let written = write_files(&files, out, self.jobs)?;
+            BuildCache::save(&manifest, out, &files, &inputs)?;
+            remove_files(out, &stale)?;
+            written
+        };
+        let (reused, rebuilt) = files.keys().cloned().partition(|path| reused.contains(path));
+        Ok(BuildReport { rebuilt, reused, written, stale, dry_run: self.dry_run })
+    }
+}
+
+/// 描画済みのファイルを `out` 以下に最大 `jobs` 並列で書き出し、書いたパスを返す。
+/// 既にあるファイルと中身が同じなら書かない（mtime を動かさない）。失敗したらパス順で最初のエラーを返す
+pub fn write_files(files: &Files, out: &Path, jobs: usize) -> Result<Vec<PathBuf>, BuildError> {
+    // フォントは別途置く（ディレクトリだけ用意する）
+    let fonts = out.join("assets/fonts");
+    fs::create_dir_all(&fonts).at(&fonts)?;
+    let changed = changed_files(files, out, jobs);
+    pool::try_map(jobs, &changed, |path| {
+        let to = out.join(path);
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent).at(parent)?;
+        }
+        fs::write(&to, &files[path]).at(&to)
+    })?;
+    Ok(changed)
+}
+
+/// `out` 以下に無い、または中身が違うファイル（パス順）
+fn changed_files(files: &Files, out: &Path, jobs: usize) -> Vec<PathBuf> {
+    let files: Vec<(&PathBuf, &Vec<u8>)> = files.iter().collect();
+    let changed = pool::map(jobs, &files, |(path, bytes)| (!fs::read(out.join(path)).is_ok_and(|old| old == **bytes)).then(|| path.to_path_buf()));
+    changed.into_iter().flatten().collect()
+}
+
+/// `out` 以下にあって `files` に無く、`keep` のどれにも当たらないファイル（消すもの。`out` からの相対パス、パス順）。
+/// 以前のビルド・別の出力先のビルドの残りや手で置いたものも含む。
+/// `keep` の各項目はパスの先頭の要素と比べる（`assets/fonts` なら assets/fonts/ 以下すべて）。シンボリックリンクは辿らない
+pub fn stale_files(files: &Files, out: &Path, keep: &[String]) -> Result<Vec<PathBuf>, BuildError> {
+    fn walk(dir: &Path, rel: &Path, found: &mut Vec<PathBuf>) -> Result<(), BuildError> {
+        for entry in fs::read_dir(dir).at(dir)? {
+            let entry = entry.at(dir)?;
+            let rel = rel.join(entry.file_name());
+            if entry.file_type().at(&entry.path())?.is_dir() {
+                walk(&entry.path(), &rel, found)?;
+            } else {
+                found.push(rel);
+            }
+        }
+        Ok(())
+    }
+    let mut found = Vec::new();
+    if out.is_dir() {
+        walk(out, Path::new(""), &mut found)?;
+    }
+    found.retain(|path| !files.contains_key(path) && !keep.iter().any(|k| path.starts_with(k.trim_end_matches('/'))));
+    found.sort();
+    Ok(found)
+}
+
+/// stale_files() の結果を消し、それで空になったディレクトリも消す（`out` 自体は残す）
+fn remove_files(out: &Path, paths: &[PathBuf]) -> Result<(), BuildError> {
+    for path in paths {
+        let file = out.join(path);
+        fs::remove_file(&file).at(&file)?;
+        for dir in path.ancestors().skip(1).take_while(|d| !d.as_os_str().is_empty()) {
+            let dir = out.join(dir);
+            if fs::read_dir(&dir).at(&dir)?.next().is_some() {
+                break;
+            }
+            fs::remove_dir(&dir).at(&dir)?;
+        }
+    }
+    Ok(())
+}
+
+/// 公開 URL（vCard と QR コードに入る）は `http(s)://<host>/...` の絶対 URL に限る（未設定は可）
+fn validate_site_url(url: &str) -> Result<(), BuildError> {
+    if url.is_empty() {
+        return Ok(());
+    }
+    let host = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")).map(|rest| rest.split(['/', '?', '#']).next().unwrap_or_default());
+    let message = match host {
+        None => "must be an absolute http(s) URL",
+        Some("") => "has no host",
+        Some(_) if url.contains(char::is_whitespace) => "must not contain whitespace",
+        Some(_) => return Ok(()),
+    };
+    Err(BuildError::InvalidUrl { url: url.to_string(), field: "site_url".into(), message: message.into() })
+}
+
+/// 2 回分の描画結果を比べ、中身が違う・片方にしか無いファイルのパスを返す（空なら再現できている）
+pub fn diff_files(a: &Files, b: &Files) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = a.iter().filter(|(path, bytes)| b.get(*path) != Some(*bytes)).map(|(path, _)| path.clone()).collect();
+    paths.extend(b.keys().filter(|path| !a.contains_key(*path)).cloned());
+    paths.sort();
+    paths
+}
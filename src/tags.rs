@@ -0,0 +1,166 @@
+/*
+ * FileName:        tags
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 10:12:40
+ * LastModified:    2026-10-18 10:12:40
+ * Reference:       8ucchiman.github.io
+ * Description:     タグ一覧・タグ別の静的ページ
+ */
+
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{cache::{Fingerprint, Incremental}, error::BuildError, add_card_inputs, html_attr, html_escape, pool, project_card_html, slugify, subpage, taxonomy::Taxonomy, url_path, Files, Project};
+
+/// 集計済みのタグ。`depth` は階層の深さ（親子順に並ぶ）。
+pub struct TagCount {
+    pub name: String,
+    pub count: usize,
+    pub depth: usize,
+}
+
+/// 全 Project のタグを集計する。子タグの Project は親タグにも数える。
+/// 並びは「親 → その子孫」の深さ優先、同じ階層ではタグ名順。
+pub fn collect_tags(projects: &[Project], tax: &Taxonomy) -> Vec<TagCount> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for p in projects {
+        for t in tax.expand(&p.tags) {
+            *counts.entry(t).or_default() += 1;
+        }
+    }
+    fn walk(name: &str, depth: usize, tax: &Taxonomy, counts: &BTreeMap<&str, usize>, out: &mut Vec<TagCount>) {
+        let Some(&count) = counts.get(name) else { return };
+        out.push(TagCount { name: name.to_string(), count, depth });
+        for child in tax.children(name) {
+            walk(&child.name, depth + 1, tax, counts, out);
+        }
+    }
+    let mut out = Vec::new();
+    for &name in counts.keys() {
+        let is_root = tax.get(name).and_then(|d| d.parent.as_deref()).is_none_or(|p| !counts.contains_key(p));
+        if is_root {
+            walk(name, 0, tax, &counts, &mut out);
+        }
+    }
+    out
+}
+
+/// フィルタ用チップ。各チップは tags/<tag>/ へのリンクなので JS 無しでも辿れる。
+/// `active` が None なら "all" を選択状態にする。子タグは親の後ろに `sub` として並ぶ。
+pub fn tag_chips_html(tags: &[TagCount], tax: &Taxonomy, active: Option<&str>, root: &str) -> String {
+    let mut s = String::new();
+    let all_cls = if active.is_none() { " active" } else { "" };
+    s.push_str(&format!(r##"<a class="chip{all_cls}" data-tag="all" href="{root}#projects">all</a>"##));
+    for t in tags {
+        let mut cls = String::new();
+        if t.depth > 0 { cls.push_str(" sub"); }
+        if active == Some(t.name.as_str()) { cls.push_str(" active"); }
+        let title = match tax.ancestors(&t.name).as_slice() {
+            [] => String::new(),
+            path => format!(
+                r#" title="{}""#,
+                html_attr(&path.iter().rev().chain([&t.name.as_str()]).map(|n| tax.label(n)).collect::<Vec<_>>().join(" › "))
+            ),
+        };
+        s.push_str(&format!(
+            r#"<a class="chip{cls}" data-tag="{}" data-depth="{}" href="{root}tags/{}/"{title}>{}</a>"#,
+            html_attr(&t.name), t.depth, url_path(&slugify(&t.name)), html_escape(tax.label(&t.name))
+        ));
+    }
+    s
+}
+
+/// tags/index.html と tags/<tag>/index.html を出力に加える。タグ別ページは最大 `jobs` 並列で描画する。
+/// 入力（タグの集計・taxonomy・そのタグの Project）が前回と同じページは `inc` から使い回す。
+/// 別のタグが同じ slug（`c` と `C++` など）になるなら、ページが上書きされないようエラーにする。
+pub fn write_tag_pages(files: &mut Files, projects: &[Project], tax: &Taxonomy, ver: &str, jobs: usize, inc: &Incremental) -> Result<(), BuildError> {
+    let tags = collect_tags(projects, tax);
+    let mut seen: BTreeMap<String, &str> = BTreeMap::new();
+    for t in &tags {
+        if let Some(other) = seen.insert(slugify(&t.name), &t.name) {
+            return Err(BuildError::config(format!(
+                "tags `{other}` and `{}` would both be written to tags/{}/ (rename one of them or make it an alias in taxonomy.toml)",
+                t.name,
+                slugify(&t.name),
+            )));
+        }
+    }
+    let dir = PathBuf::from("tags");
+    // 一覧・チップ・パンくずが使う項目（タグ名・件数・深さ・表示名・説明・祖先）
+    let fingerprint = || {
+        let mut fp = Fingerprint::new();
+        fp.add(ver).add((tags.len() as u64).to_le_bytes());
+        for t in &tags {
+            let ancestors: Vec<&str> = tax.ancestors(&t.name).into_iter().map(|a| tax.label(a)).collect();
+            let description = tax.get(&t.name).map_or("", |d| d.description.as_str());
+            fp.add(&t.name).add(t.count.to_string()).add(t.depth.to_string()).add(tax.label(&t.name)).add(description).add_list(&ancestors);
+        }
+        fp
+    };
+
+    // 一覧（階層・件数付き）
+    let path = dir.join("index.html");
+    let index = inc.get_or_render(&path, fingerprint().finish(), || {
+        let mut items = String::new();
+        for t in &tags {
+            items.push_str(&format!(
+                r#"<li class="depth-{}"><a class="chip" href="{}/">{}<span class="chip-count">{}</span></a>{}</li>"#,
+                t.depth,
+                url_path(&slugify(&t.name)),
+                html_escape(tax.label(&t.name)),
+                t.count,
+                tag_description(tax, &t.name),
+            ));
+        }
+        let body = format!(r#"<h3>tags</h3>
+<ul class="tag-index">{items}</ul>"#);
+        subpage("tags", "../", ver, &body).into_bytes()
+    });
+    files.insert(path, index);
+
+    // タグ別ページ
+    let pages = pool::map(jobs, &tags, |t| {
+        let members: Vec<&Project> = projects.iter().filter(|p| tax.expand(&p.tags).contains(&t.name.as_str())).collect();
+        let path = dir.join(slugify(&t.name)).join("index.html");
+        let mut fp = fingerprint();
+        fp.add(&t.name).add((members.len() as u64).to_le_bytes());
+        for p in &members {
+            add_card_inputs(&mut fp, p);
+        }
+        let input = fp.finish();
+        let page = inc.get_or_render(&path, input, || {
+            let cards: String = members.iter().map(|p| project_card_html(p, "../../")).collect();
+            let crumbs: String = tax
+                .ancestors(&t.name)
+                .iter()
+                .rev()
+                .map(|a| format!(r#"<a href="../{}/">{}</a> › "#, url_path(&slugify(a)), html_escape(tax.label(a))))
+                .collect();
+            let label = tax.label(&t.name);
+            let body = format!(
+                r#"<p class="about-kicker">{crumbs}</p>
+<h3>#{l}</h3>
+{desc}
+<p>{n} project{plural}</p>
+<nav class="proj-controls" aria-label="project filters">{chips}</nav>
+<div class="proj-grid">{cards}</div>"#,
+                l = html_escape(label),
+                desc = tag_description(tax, &t.name),
+                n = t.count,
+                plural = if t.count == 1 { "" } else { "s" },
+                chips = tag_chips_html(&tags, tax, Some(&t.name), "../../"),
+            );
+            subpage(&format!("#{label}"), "../../", ver, &body).into_bytes()
+        });
+        (path, page)
+    });
+    files.extend(pages);
+    Ok(())
+}
+
+fn tag_description(tax: &Taxonomy, name: &str) -> String {
+    match tax.get(name) {
+        Some(d) if !d.description.is_empty() => format!(r#"<p class="tag-desc">{}</p>"#, html_escape(&d.description)),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,302 @@
+/*
+ * FileName:        taxonomy
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 11:40:03
+ * LastModified:    2026-10-18 11:40:03
+ * Reference:       8ucchiman.github.io
+ * Description:     タグの正規名・表示名・別名・親子関係（taxonomy.toml）
+ */
+
+
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use crate::{error::BuildError, toml, Project};
+
+/// taxonomy.toml の 1 エントリ
+///
+/// ```toml
+/// [opengl]
+/// label = "OpenGL"
+/// parent = "3d"
+/// aliases = ["gl"]
+/// description = "Realtime rendering with OpenGL / GLFW."
+/// ```
+#[derive(Clone, Debug)]
+pub struct TagDef {
+    pub name: String,
+    pub label: String,
+    pub parent: Option<String>,
+    pub aliases: Vec<String>,
+    pub description: String,
+}
+
+#[derive(Default)]
+pub struct Taxonomy {
+    defs: BTreeMap<String, TagDef>,
+    /// 正規化した名前・別名 → 正規名
+    lookup: BTreeMap<String, String>,
+}
+
+/// 表記ゆれの吸収: 全角英数→半角、半角カナ→全角、小文字化、空白と `_` は `-` に。
+pub fn normalize(raw: &str) -> String {
+    const HALF_KANA: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン゛゜";
+    let mut folded: Vec<char> = Vec::new();
+    for c in raw.chars() {
+        let c = match c as u32 {
+            0xFF01..=0xFF5E => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            0x3000 => ' ',
+            0xFF61..=0xFF9F => HALF_KANA.chars().nth((c as u32 - 0xFF61) as usize).unwrap_or(c),
+            _ => c,
+        };
+        // 半角カナの濁点・半濁点は直前の文字に合成する
+        match (c, folded.last().copied()) {
+            ('゛', Some('ウ')) => { folded.pop(); folded.push('ヴ'); }
+            ('゛', Some(p)) if "カキクケコサシスセソタチツテトハヒフヘホ".contains(p) => {
+                folded.pop();
+                folded.push(char::from_u32(p as u32 + 1).unwrap_or(p));
+            }
+            ('゜', Some(p)) if "ハヒフヘホ".contains(p) => {
+                folded.pop();
+                folded.push(char::from_u32(p as u32 + 2).unwrap_or(p));
+            }
+            _ => folded.push(c),
+        }
+    }
+    let mut s = String::new();
+    for c in folded.into_iter().flat_map(char::to_lowercase) {
+        if c.is_whitespace() || c == '_' {
+            if !s.is_empty() && !s.ends_with('-') { s.push('-'); }
+        } else {
+            s.push(c);
+        }
+    }
+    while s.ends_with('-') { s.pop(); }
+    s
+}
+
+impl Taxonomy {
+    /// ファイルが無ければ空の taxonomy（全タグが未知扱い）
+    pub fn load(path: &Path) -> Result<Self, BuildError> {
+        match fs::read_to_string(path) {
+            Ok(src) => Self::from_toml(&src).map_err(|e| BuildError::toml(path, &src, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(BuildError::io(path, e)),
+        }
+    }
+
+    /// 内容の誤り（別名の衝突・未知の親・循環）は該当するタグの `[key]`（か `key = `）の行を指す
+    pub fn from_toml(src: &str) -> Result<Self, toml::Error> {
+        let doc = toml::parse(src)?;
+        // 正規名 → 書かれたキー（エラーの位置を探すのに使う）
+        let keys: BTreeMap<String, &str> = doc.keys().map(|k| (normalize(k), k.as_str())).collect();
+        let invalid = |name: &str, msg: String| {
+            let key = keys.get(name).copied().unwrap_or(name);
+            let line = src
+                .lines()
+                .position(|l| {
+                    let l = l.trim();
+                    l == format!("[{key}]")
+                        || l == format!("[\"{key}\"]")
+                        || l.strip_prefix(key).is_some_and(|rest| rest.trim_start().starts_with('='))
+                })
+                .map_or(0, |i| i + 1);
+            toml::Error { line, col: usize::from(line > 0), msg }
+        };
+        let mut tax = Self::default();
+        for (key, v) in &doc {
+            let Some(t) = v.as_table() else {
+                return Err(invalid(&normalize(key), format!("tag `{key}` must be a table")));
+            };
+            let name = normalize(key);
+            let def = TagDef {
+                label: t.get("label").and_then(|v| v.as_str()).unwrap_or(key).to_string(),
+                parent: t.get("parent").and_then(|v| v.as_str()).map(normalize),
+                aliases: t.get("aliases").map(|v| v.as_str_list()).unwrap_or_default(),
+                description: t.get("description").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                name: name.clone(),
+            };
+            for alias in std::iter::once(key).chain(&def.aliases) {
+                if let Some(prev) = tax.lookup.insert(normalize(alias), name.clone())
+                    && prev != name
+                {
+                    return Err(invalid(&name, format!("alias `{alias}` is claimed by both `{prev}` and `{name}`")));
+                }
+            }
+            tax.defs.insert(name, def);
+        }
+        // 親タグは別名でも書けるので正規名に直す
+        let parents: Vec<(String, String)> = tax
+            .defs
+            .values()
+            .filter_map(|d| d.parent.as_ref().map(|p| (d.name.clone(), p.clone())))
+            .collect();
+        for (name, parent) in parents {
+            let Some(p) = tax.resolve(&parent) else {
+                return Err(invalid(&name, format!("tag `{name}` has unknown parent `{parent}`")));
+            };
+            let p = p.name.clone();
+            if let Some(d) = tax.defs.get_mut(&name) { d.parent = Some(p); }
+        }
+        // 循環チェック
+        for def in tax.defs.values() {
+            let mut seen = vec![def.name.as_str()];
+            let mut cur = def;
+            while let Some(p) = cur.parent.as_deref().and_then(|p| tax.defs.get(p)) {
+                if seen.contains(&p.name.as_str()) {
+                    return Err(invalid(&p.name, format!("tag hierarchy has a cycle through `{}`", p.name)));
+                }
+                seen.push(&p.name);
+                cur = p;
+            }
+        }
+        Ok(tax)
+    }
+
+    pub fn resolve(&self, raw: &str) -> Option<&TagDef> {
+        self.lookup.get(&normalize(raw)).and_then(|name| self.defs.get(name))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TagDef> {
+        self.defs.get(name)
+    }
+
+    /// Project に出てくる未知のタグを ad-hoc に登録し、警告メッセージを返す。
+    pub fn register_unknown(&mut self, projects: &[Project]) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for p in projects {
+            for raw in &p.tags {
+                if self.resolve(raw).is_some() {
+                    continue;
+                }
+                let name = normalize(raw);
+                if name.is_empty() {
+                    warnings.push(format!("empty tag in project `{}`", p.title));
+                    continue;
+                }
+                warnings.push(format!("unknown tag `{raw}` in project `{}` (add it to taxonomy.toml)", p.title));
+                self.lookup.insert(name.clone(), name.clone());
+                self.defs.insert(name.clone(), TagDef {
+                    label: raw.trim().to_string(),
+                    name,
+                    parent: None,
+                    aliases: Vec::new(),
+                    description: String::new(),
+                });
+            }
+        }
+        warnings
+    }
+
+    /// タグ列を正規名に置き換える（重複は除く）。`register_unknown` の後に呼ぶこと。
+    pub fn canonical_tags(&self, tags: &[String]) -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        for t in tags {
+            if let Some(def) = self.resolve(t)
+                && !out.contains(&def.name)
+            {
+                out.push(def.name.clone());
+            }
+        }
+        out
+    }
+
+    /// 親をたどった祖先（近い順）
+    pub fn ancestors(&self, name: &str) -> Vec<&str> {
+        let mut out = Vec::new();
+        let mut cur = self.defs.get(name);
+        while let Some(parent) = cur.and_then(|d| d.parent.as_deref()) {
+            let Some(p) = self.defs.get(parent) else { break };
+            if out.contains(&p.name.as_str()) { break; }
+            out.push(p.name.as_str());
+            cur = Some(p);
+        }
+        out
+    }
+
+    /// タグ + その祖先（絞り込み用: `opengl` の Project は `3d` にも含まれる）
+    pub fn expand<'t>(&'t self, tags: &'t [String]) -> Vec<&'t str> {
+        let mut out: Vec<&str> = Vec::new();
+        for t in tags.iter().map(String::as_str) {
+            for x in std::iter::once(t).chain(self.ancestors(t)) {
+                if !out.contains(&x) { out.push(x); }
+            }
+        }
+        out
+    }
+
+    pub fn children(&self, name: &str) -> Vec<&TagDef> {
+        self.defs.values().filter(|d| d.parent.as_deref() == Some(name)).collect()
+    }
+
+    pub fn label<'t>(&'t self, name: &'t str) -> &'t str {
+        self.defs.get(name).map(|d| d.label.as_str()).unwrap_or(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &str = r#"
+[3d]
+label = "3D"
+
+[opengl]
+label = "OpenGL"
+parent = "3D"
+aliases = ["gl", "Open GL"]
+"#;
+
+    #[test]
+    fn normalize_folds_width_case_and_spacing() {
+        assert_eq!(normalize("Ｒｕｓｔ"), "rust");
+        assert_eq!(normalize("  Machine_Learning　Ops "), "machine-learning-ops");
+        assert_eq!(normalize("ｶﾞｿﾞｳ ｼｮﾘ"), "ガゾウ-ショリ");
+        assert_eq!(normalize("ﾎﾟｲﾝﾄ"), "ポイント");
+    }
+
+    #[test]
+    fn aliases_and_parents_resolve_to_canonical_names() {
+        let tax = Taxonomy::from_toml(SRC).unwrap();
+        for raw in ["opengl", "OpenGL", "GL", "open gl", "ｏｐｅｎ＿ｇｌ"] {
+            assert_eq!(tax.resolve(raw).map(|d| d.name.as_str()), Some("opengl"), "{raw}");
+        }
+        // 親は別名（ラベル）で書いても正規名になる
+        assert_eq!(tax.get("opengl").unwrap().parent.as_deref(), Some("3d"));
+        assert_eq!(tax.ancestors("opengl"), ["3d"]);
+        let tags = vec!["GL".to_string(), "opengl".to_string(), "3D".to_string()];
+        assert_eq!(tax.canonical_tags(&tags), ["opengl", "3d"]);
+        assert_eq!(tax.label("opengl"), "OpenGL");
+    }
+
+    #[test]
+    fn unknown_tags_are_registered_with_a_warning() {
+        let mut tax = Taxonomy::from_toml(SRC).unwrap();
+        let p = Project { title: "Alpha".into(), tags: vec!["gl".into(), "Ｒｕｓｔ".into(), " ".into()], ..Default::default() };
+        let warnings = tax.register_unknown(std::slice::from_ref(&p));
+        assert_eq!(warnings, ["unknown tag `Ｒｕｓｔ` in project `Alpha` (add it to taxonomy.toml)", "empty tag in project `Alpha`"]);
+        assert_eq!(tax.label("rust"), "Ｒｕｓｔ");
+        assert_eq!(tax.canonical_tags(&p.tags), ["opengl", "rust"]);
+        // 2 回目からは既知
+        assert!(tax.register_unknown(&[Project { tags: vec!["rust".into()], ..p }]).is_empty());
+    }
+
+    fn error(src: &str) -> toml::Error {
+        let Err(e) = Taxonomy::from_toml(src) else { panic!("accepted:\n{src}") };
+        e
+    }
+
+    #[test]
+    fn cycles_unknown_parents_and_alias_clashes_are_errors() {
+        let e = error("[a]\nparent = \"b\"\n\n[b]\nparent = \"c\"\n\n[c]\nparent = \"a\"\n");
+        assert!(e.msg.contains("cycle"), "{}", e.msg);
+        assert!(e.line > 0);
+
+        let e = error("[a]\n\n[b]\nparent = \"nope\"\n");
+        assert_eq!((e.line, e.msg.as_str()), (3, "tag `b` has unknown parent `nope`"));
+
+        let e = error("[a]\naliases = [\"x\"]\n\n[b]\naliases = [\"X\"]\n");
+        assert!(e.msg.contains("alias `X` is claimed by both `a` and `b`"), "{}", e.msg);
+        assert_eq!(e.line, 4);
+    }
+}
@@ -0,0 +1,466 @@
+/*
+ * FileName:        toml
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-18 11:02:17
+ * LastModified:    2026-10-18 11:02:17
+ * Reference:       https://toml.io/en/v1.0.0
+ * Description:     依存なしの最小 TOML パーサ（taxonomy.toml / Cargo.toml 用）
+ */
+
+
+use std::{collections::BTreeMap, fmt};
+
+pub type Table = BTreeMap<String, Value>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    /// 日付・時刻はそのままの文字列で保持する（解釈は呼び出し側で）
+    Datetime(String),
+    Array(Vec<Value>),
+    Table(Table),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) | Value::Datetime(s) => Some(s),
+            _ => None,
+        }
+    }
+    pub fn as_table(&self) -> Option<&Table> {
+        match self {
+            Value::Table(t) => Some(t),
+            _ => None,
+        }
+    }
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(a) => Some(a),
+            _ => None,
+        }
+    }
+    /// 文字列の配列を取り出す（文字列以外の要素は無視）
+    pub fn as_str_list(&self) -> Vec<String> {
+        self.as_array()
+            .map(|a| a.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    }
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Boolean(_) => "boolean",
+            Value::Datetime(_) => "datetime",
+            Value::Array(_) => "array",
+            Value::Table(_) => "table",
+        }
+    }
+}
+
+/// パースエラー。line / col は 1 始まり。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    pub line: usize,
+    pub col: usize,
+    pub msg: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at line {}, column {}", self.msg, self.line, self.col)
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub fn parse(src: &str) -> Result<Table, Error> {
+    Parser { s: src.chars().collect(), pos: 0 }.document()
+}
+
+struct Parser {
+    s: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn err<T>(&self, msg: impl Into<String>) -> Result<T, Error> {
+        let (mut line, mut col) = (1, 1);
+        for &c in &self.s[..self.pos.min(self.s.len())] {
+            if c == '\n' { line += 1; col = 1; } else { col += 1; }
+        }
+        Err(Error { line, col, msg: msg.into() })
+    }
+
+    fn peek(&self) -> Option<char> { self.s.get(self.pos).copied() }
+    fn peek_at(&self, n: usize) -> Option<char> { self.s.get(self.pos + n).copied() }
+    fn starts_with(&self, lit: &str) -> bool {
+        lit.chars().enumerate().all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    fn skip_ws(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) { self.pos += 1; }
+    }
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while !matches!(self.peek(), None | Some('\n')) { self.pos += 1; }
+        }
+    }
+    /// 空白・コメント・改行をまとめて読み飛ばす（配列の中など）
+    fn skip_ws_nl(&mut self) {
+        loop {
+            self.skip_ws();
+            self.skip_comment();
+            match self.peek() {
+                Some('\n') => self.pos += 1,
+                Some('\r') if self.peek_at(1) == Some('\n') => self.pos += 2,
+                _ => break,
+            }
+        }
+    }
+    fn expect_eol(&mut self) -> Result<(), Error> {
+        self.skip_ws();
+        self.skip_comment();
+        match self.peek() {
+            None => Ok(()),
+            Some('\n') => { self.pos += 1; Ok(()) }
+            Some('\r') if self.peek_at(1) == Some('\n') => { self.pos += 2; Ok(()) }
+            Some(c) => self.err(format!("expected end of line, found `{c}`")),
+        }
+    }
+
+    fn document(mut self) -> Result<Table, Error> {
+        let mut root = Table::new();
+        let mut current: Vec<String> = Vec::new();
+        loop {
+            self.skip_ws_nl();
+            let Some(c) = self.peek() else { break };
+            if c == '[' {
+                let array = self.peek_at(1) == Some('[');
+                self.pos += if array { 2 } else { 1 };
+                self.skip_ws();
+                let path = self.key_path()?;
+                self.skip_ws();
+                let close = if array { "]]" } else { "]" };
+                if !self.starts_with(close) {
+                    return self.err(format!("expected `{close}` after table name"));
+                }
+                self.pos += close.len();
+                if array {
+                    let (last, parent) = path.split_last().expect("key path is never empty");
+                    let t = self.descend(&mut root, parent)?;
+                    match t.entry(last.clone()).or_insert_with(|| Value::Array(Vec::new())) {
+                        Value::Array(items) => items.push(Value::Table(Table::new())),
+                        _ => return self.err(format!("key `{last}` is not an array of tables")),
+                    }
+                } else {
+                    let (last, parent) = path.split_last().expect("key path is never empty");
+                    let t = self.descend(&mut root, parent)?;
+                    match t.entry(last.clone()).or_insert_with(|| Value::Table(Table::new())) {
+                        Value::Table(_) => {}
+                        _ => return self.err(format!("key `{last}` is already defined as a value")),
+                    }
+                }
+                self.expect_eol()?;
+                current = path;
+            } else {
+                let path = self.key_path()?;
+                self.skip_ws();
+                if self.peek() != Some('=') {
+                    return self.err("expected `=` after key");
+                }
+                self.pos += 1;
+                self.skip_ws();
+                let value = self.value()?;
+                let full: Vec<String> = current.iter().chain(&path).cloned().collect();
+                self.insert(&mut root, &full, value)?;
+                self.expect_eol()?;
+            }
+        }
+        Ok(root)
+    }
+
+    /// テーブルを辿る（なければ作る）。配列テーブルは最後の要素に入る。
+    fn descend<'t>(&self, mut t: &'t mut Table, keys: &[String]) -> Result<&'t mut Table, Error> {
+        for k in keys {
+            let v = t.entry(k.clone()).or_insert_with(|| Value::Table(Table::new()));
+            t = match v {
+                Value::Table(inner) => inner,
+                Value::Array(items) => match items.last_mut() {
+                    Some(Value::Table(inner)) => inner,
+                    _ => return self.err(format!("key `{k}` is not a table")),
+                },
+                other => return self.err(format!("key `{k}` is a {}, not a table", other.type_name())),
+            };
+        }
+        Ok(t)
+    }
+
+    fn insert(&self, root: &mut Table, path: &[String], value: Value) -> Result<(), Error> {
+        let (last, parent) = path.split_last().expect("key path is never empty");
+        let t = self.descend(root, parent)?;
+        if t.contains_key(last) {
+            return self.err(format!("duplicate key `{}`", path.join(".")));
+        }
+        t.insert(last.clone(), value);
+        Ok(())
+    }
+
+    fn key_path(&mut self) -> Result<Vec<String>, Error> {
+        let mut path = vec![self.key()?];
+        loop {
+            self.skip_ws();
+            if self.peek() != Some('.') { break; }
+            self.pos += 1;
+            self.skip_ws();
+            path.push(self.key()?);
+        }
+        Ok(path)
+    }
+
+    fn key(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Some('"') => self.basic_string(),
+            Some('\'') => self.literal_string(),
+            _ => {
+                let start = self.pos;
+                while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                    self.pos += 1;
+                }
+                if start == self.pos {
+                    return self.err("expected a key");
+                }
+                Ok(self.s[start..self.pos].iter().collect())
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        match self.peek() {
+            Some('"') if self.starts_with("\"\"\"") => self.ml_basic_string().map(Value::String),
+            Some('"') => self.basic_string().map(Value::String),
+            Some('\'') if self.starts_with("'''") => self.ml_literal_string().map(Value::String),
+            Some('\'') => self.literal_string().map(Value::String),
+            Some('[') => self.array(),
+            Some('{') => self.inline_table(),
+            Some('t') if self.starts_with("true") => { self.pos += 4; Ok(Value::Boolean(true)) }
+            Some('f') if self.starts_with("false") => { self.pos += 5; Ok(Value::Boolean(false)) }
+            Some(c) if c.is_ascii_digit() || matches!(c, '+' | '-' | 'i' | 'n') => self.number_or_date(),
+            Some(c) => self.err(format!("unexpected `{c}` at start of value")),
+            None => self.err("expected a value, found end of file"),
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, Error> {
+        self.pos += 1; // [
+        let mut items = Vec::new();
+        loop {
+            self.skip_ws_nl();
+            if self.peek() == Some(']') { self.pos += 1; break; }
+            items.push(self.value()?);
+            self.skip_ws_nl();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => { self.pos += 1; break; }
+                _ => return self.err("expected `,` or `]` in array"),
+            }
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn inline_table(&mut self) -> Result<Value, Error> {
+        self.pos += 1; // {
+        let mut t = Table::new();
+        self.skip_ws();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Value::Table(t));
+        }
+        loop {
+            self.skip_ws();
+            let path = self.key_path()?;
+            self.skip_ws();
+            if self.peek() != Some('=') {
+                return self.err("expected `=` in inline table");
+            }
+            self.pos += 1;
+            self.skip_ws();
+            let v = self.value()?;
+            self.insert(&mut t, &path, v)?;
+            self.skip_ws();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => { self.pos += 1; break; }
+                _ => return self.err("expected `,` or `}` in inline table"),
+            }
+        }
+        Ok(Value::Table(t))
+    }
+
+    fn escape(&mut self) -> Result<char, Error> {
+        let c = self.peek();
+        self.pos += 1;
+        Ok(match c {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some(u @ ('u' | 'U')) => {
+                let n = if u == 'u' { 4 } else { 8 };
+                let hex: String = self.s.get(self.pos..self.pos + n).unwrap_or_default().iter().collect();
+                let cp = u32::from_str_radix(&hex, 16).ok().filter(|_| hex.len() == n);
+                self.pos += n;
+                match cp.and_then(char::from_u32) {
+                    Some(ch) => ch,
+                    None => return self.err(format!("invalid unicode escape `\\{u}{hex}`")),
+                }
+            }
+            Some(c) => return self.err(format!("invalid escape `\\{c}`")),
+            None => return self.err("unterminated string"),
+        })
+    }
+
+    fn basic_string(&mut self) -> Result<String, Error> {
+        self.pos += 1;
+        let mut s = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => { self.pos += 1; return Ok(s); }
+                Some('\\') => { self.pos += 1; s.push(self.escape()?); }
+                Some('\n') | None => return self.err("unterminated string"),
+                Some(c) => { s.push(c); self.pos += 1; }
+            }
+        }
+    }
+
+    fn literal_string(&mut self) -> Result<String, Error> {
+        self.pos += 1;
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some('\'') => {
+                    let s = self.s[start..self.pos].iter().collect();
+                    self.pos += 1;
+                    return Ok(s);
+                }
+                Some('\n') | None => return self.err("unterminated string"),
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_first_newline(&mut self) {
+        if self.peek() == Some('\n') { self.pos += 1; }
+        else if self.starts_with("\r\n") { self.pos += 2; }
+    }
+
+    fn ml_basic_string(&mut self) -> Result<String, Error> {
+        self.pos += 3;
+        self.skip_first_newline();
+        let mut s = String::new();
+        loop {
+            if self.starts_with("\"\"\"") {
+                self.pos += 3;
+                // 閉じ引用符の直前の `"` は内容に含める（`""""` など）
+                while self.peek() == Some('"') { s.push('"'); self.pos += 1; }
+                return Ok(s);
+            }
+            match self.peek() {
+                Some('\\') => {
+                    self.pos += 1;
+                    // 行末のバックスラッシュ: 改行と続く空白を捨てる
+                    let save = self.pos;
+                    self.skip_ws();
+                    if matches!(self.peek(), Some('\n' | '\r')) {
+                        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) { self.pos += 1; }
+                    } else {
+                        self.pos = save;
+                        s.push(self.escape()?);
+                    }
+                }
+                Some(c) => { s.push(c); self.pos += 1; }
+                None => return self.err("unterminated multi-line string"),
+            }
+        }
+    }
+
+    fn ml_literal_string(&mut self) -> Result<String, Error> {
+        self.pos += 3;
+        self.skip_first_newline();
+        let start = self.pos;
+        loop {
+            if self.starts_with("'''") {
+                let mut end = self.pos;
+                self.pos += 3;
+                while self.peek() == Some('\'') { self.pos += 1; end += 1; }
+                return Ok(self.s[start..end].iter().collect());
+            }
+            if self.peek().is_none() {
+                return self.err("unterminated multi-line string");
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn number_or_date(&mut self) -> Result<Value, Error> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            // 日時の "1979-05-27 07:32:00" のような空白区切りも 1 トークンとして扱う
+            let date_space = c == ' '
+                && self.pos > start
+                && self.s[start..self.pos].iter().filter(|&&c| c == '-').count() == 2
+                && self.peek_at(1).is_some_and(|d| d.is_ascii_digit())
+                && self.s[start..self.pos].iter().all(|c| c.is_ascii_digit() || *c == '-');
+            if c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '_' | '.' | ':') || date_space {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        let tok: String = self.s[start..self.pos].iter().collect();
+        let is_date = {
+            let b = tok.as_bytes();
+            (b.len() >= 10 && b[..4].iter().all(u8::is_ascii_digit) && b[4] == b'-' && b[7] == b'-')
+                || (b.len() >= 5 && b[..2].iter().all(u8::is_ascii_digit) && b[2] == b':')
+        };
+        if is_date {
+            return Ok(Value::Datetime(tok));
+        }
+        match tok.as_str() {
+            "inf" | "+inf" => return Ok(Value::Float(f64::INFINITY)),
+            "-inf" => return Ok(Value::Float(f64::NEG_INFINITY)),
+            "nan" | "+nan" | "-nan" => return Ok(Value::Float(f64::NAN)),
+            _ => {}
+        }
+        let clean = tok.replace('_', "");
+        let (sign, digits) = match clean.strip_prefix('-') {
+            Some(d) => (-1, d),
+            None => (1, clean.strip_prefix('+').unwrap_or(&clean)),
+        };
+        for (prefix, radix) in [("0x", 16), ("0o", 8), ("0b", 2)] {
+            if let Some(d) = digits.strip_prefix(prefix) {
+                return match i64::from_str_radix(d, radix) {
+                    Ok(n) => Ok(Value::Integer(sign * n)),
+                    Err(_) => { self.pos = start; self.err(format!("invalid number `{tok}`")) }
+                };
+            }
+        }
+        if let Ok(n) = clean.parse::<i64>() {
+            return Ok(Value::Integer(n));
+        }
+        if clean.contains(['.', 'e', 'E'])
+            && let Ok(f) = clean.parse::<f64>()
+        {
+            return Ok(Value::Float(f));
+        }
+        self.pos = start;
+        self.err(format!("invalid value `{tok}`"))
+    }
+}
@@ -0,0 +1,159 @@
+/*
+ * FileName:        vcard
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 15:48:10
+ * LastModified:    2026-10-19 15:48:10
+ * Reference:       8ucchiman.github.io
+ * Description:     連絡先の vCard 4.0（contact.vcf）
+ */
+
+
+use crate::{about::About, contacts::Contact, percent_decode, url_path};
+
+/// About と連絡先から vCard 4.0（RFC 6350）を作る。
+/// メールアドレスはページ側の難読化と関係なくそのまま入るので、Site の contact_qr が true のときだけ出力する。
+/// `portrait` はポートレートの中身（コピーできたときだけ）。PHOTO は `site_url` があれば公開 URL、無ければ data: URI にする。
+pub fn render(about: &About, contacts: &[Contact], site_url: &str, portrait: Option<&[u8]>) -> String {
+    let mut lines = vec!["BEGIN:VCARD".to_string(), "VERSION:4.0".to_string()];
+    lines.push(format!("FN:{}", escape(&about.name)));
+    if !about.roles.is_empty() {
+        lines.push(format!("TITLE:{}", escape(&about.roles.join(" / "))));
+    }
+    if let Some(bytes) = portrait {
+        if !site_url.is_empty() {
+            lines.push(format!("PHOTO:{}/{}", site_url.trim_end_matches('/'), url_path(about.portrait.trim_start_matches('/'))));
+        } else if let Some(media_type) = media_type(&about.portrait) {
+            lines.push(format!("PHOTO:data:{media_type};base64,{}", base64(bytes)));
+        }
+    }
+    if !site_url.is_empty() {
+        lines.push(format!("URL:{site_url}"));
+    }
+    for c in contacts {
+        match c.url.strip_prefix("mailto:") {
+            Some(to) => lines.extend(addresses(to).into_iter().map(|addr| format!("EMAIL:{}", escape(&addr)))),
+            None if c.url.trim_end_matches('/') == site_url.trim_end_matches('/') => {}
+            // 連絡先アプリが取り込めるよう、SNS なども標準の URL に種類を付けて入れる
+            None => lines.push(format!("URL;TYPE={}:{}", param(&c.kind), c.url)),
+        }
+    }
+    lines.push("END:VCARD".to_string());
+    lines.iter().map(|l| fold(l) + "\r\n").collect()
+}
+
+/// `mailto:` の後ろ（`a%40b.c,d@e.f?subject=Hi`）からアドレスを取り出す。`?` 以降のヘッダは捨てる
+fn addresses(to: &str) -> Vec<String> {
+    let to = to.split('?').next().unwrap_or_default();
+    to.split(',').map(|a| percent_decode(a).trim().to_string()).filter(|a| !a.is_empty()).collect()
+}
+
+/// 拡張子から画像のメディアタイプ（知らないものは None で PHOTO を出さない）
+fn media_type(path: &str) -> Option<&'static str> {
+    let ext = path.rsplit_once('.')?.1.to_ascii_lowercase();
+    Some(match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        _ => return None,
+    })
+}
+
+/// RFC 4648 の base64（パディングあり）
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// テキスト値のエスケープ（\ , ; 改行）
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace(',', "\\,").replace(';', "\\;").replace('\n', "\\n")
+}
+
+/// パラメータ値に使えない文字を落とす
+fn param(s: &str) -> String {
+    s.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '-').collect()
+}
+
+/// 75 オクテットごとに折り返す（UTF-8 の文字の途中では切らない）
+fn fold(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn about() -> About {
+        About { name: "me".into(), portrait: "assets/my photo.png".into(), ..Default::default() }
+    }
+
+    #[test]
+    fn base64_matches_rfc4648_vectors() {
+        let got: Vec<String> = ["", "f", "fo", "foo", "foob", "fooba", "foobar"].iter().map(|s| base64(s.as_bytes())).collect();
+        assert_eq!(got, ["", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy"]);
+    }
+
+    fn contact(kind: &str, url: &str) -> Contact {
+        Contact { kind: kind.into(), url: url.into(), ..Default::default() }
+    }
+
+    #[test]
+    fn emails_are_decoded_and_links_are_typed_urls() {
+        let contacts = [
+            contact("email", "mailto:me%40example.com?subject=Hi&body=x"),
+            contact("work", "mailto:a@example.com,b%2Bc@example.com"),
+            contact("github", "https://github.com/me"),
+            contact("blog", "https://me.example"),
+        ];
+        let card = render(&about(), &contacts, "https://me.example/", None);
+        assert_eq!(
+            card,
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nFN:me\r\nURL:https://me.example/\r\nEMAIL:me@example.com\r\n\
+             EMAIL:a@example.com\r\nEMAIL:b+c@example.com\r\nURL;TYPE=github:https://github.com/me\r\nEND:VCARD\r\n"
+        );
+    }
+
+    #[test]
+    fn long_lines_fold_at_75_octets_between_characters() {
+        let about = About { name: "山".repeat(40), roles: vec!["Robotics, CV; Rust".into()], ..Default::default() };
+        let card = render(&about, &[], "", None);
+        let lines: Vec<&str> = card.split("\r\n").collect();
+        // "FN:" + 3 バイトの文字 24 個 = 75 オクテット、続きは先頭の空白 1 + 3 バイト × 16
+        assert_eq!(lines[2], format!("FN:{}", "山".repeat(24)));
+        assert_eq!(lines[3], format!(" {}", "山".repeat(16)));
+        assert!(lines.iter().all(|l| l.len() <= 75));
+        assert_eq!(lines[4], "TITLE:Robotics\\, CV\\; Rust");
+    }
+
+    #[test]
+    fn photo_is_a_url_or_a_data_uri() {
+        let card = render(&about(), &[], "https://me.example/", Some(b"png"));
+        assert!(card.contains("PHOTO:https://me.example/assets/my%20photo.png\r\n"), "{card}");
+        let card = render(&about(), &[], "", Some(b"png"));
+        assert!(card.contains("PHOTO:data:image/png;base64,cG5n\r\n"), "{card}");
+        assert!(!render(&about(), &[], "https://me.example/", None).contains("PHOTO"));
+    }
+}
@@ -0,0 +1,219 @@
+/*
+ * FileName:        output
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 23:10:04
+ * LastModified:    2026-10-19 23:10:04
+ * Reference:       8ucchiman.github.io
+ * Description:     サイト全体の描画と出力先への書き出しのテスト（古いファイルの削除・keep・--dry-run）
+ */
+
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bucchiman_github_io::{
+    contacts::{Contact, Obfuscate},
+    stale_files, BuildContext, Files, Project, Site, SiteBuilder,
+};
+
+// ---- fixtures ----
+
+/// テストごとの空の作業ディレクトリ
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bucchiman-output-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn put(dir: &Path, path: &str, text: &str) {
+    let to = dir.join(path);
+    fs::create_dir_all(to.parent().unwrap()).unwrap();
+    fs::write(to, text).unwrap();
+}
+
+fn site(titles: &[&str]) -> Site {
+    Site {
+        projects: titles.iter().map(|t| Project { title: t.to_string(), ..Default::default() }).collect(),
+        keep: vec!["CNAME".into(), "assets/fonts/".into()],
+        ..Default::default()
+    }
+}
+
+fn builder<'s>(site: &'s Site, root: &Path) -> SiteBuilder<'s> {
+    SiteBuilder::new(site).context(BuildContext { root: root.to_path_buf(), ..Default::default() }).jobs(2)
+}
+
+fn paths(xs: &[&str]) -> Vec<PathBuf> {
+    xs.iter().map(PathBuf::from).collect()
+}
+
+// ---- stale_files ----
+
+#[test]
+fn stale_files_removes_everything_not_built_or_kept() {
+    let out = temp_dir("stale");
+    for p in ["index.html", "old/index.html", "notes.txt", "CNAME", "assets/fonts/a.woff2", "assets/fonts2/b.woff2"] {
+        put(&out, p, "x");
+    }
+    let files: Files = [(PathBuf::from("index.html"), b"x".to_vec())].into();
+    let keep = ["CNAME".to_string(), "assets/fonts/".to_string()];
+
+    // 今回出したもの・keep に当たるものだけ残す。keep はパスの要素単位で比べる
+    let stale = stale_files(&files, &out, &keep).unwrap();
+    assert_eq!(stale, paths(&["assets/fonts2/b.woff2", "notes.txt", "old/index.html"]));
+}
+
+#[test]
+fn stale_files_of_a_missing_out_is_empty() {
+    let out = temp_dir("missing").join("dist");
+    assert!(stale_files(&Files::new(), &out, &[]).unwrap().is_empty());
+}
+
+// ---- SiteBuilder::write_to ----
+
+#[test]
+fn write_to_removes_what_the_build_no_longer_produces() {
+    let root = temp_dir("prune");
+    let out = root.join("dist");
+    builder(&site(&["Alpha", "Beta"]), &root).write_to(&out).unwrap();
+    put(&out, "CNAME", "example.com");
+    put(&out, "stray.txt", "mine");
+    assert!(out.join("projects/beta/index.html").is_file());
+
+    let report = builder(&site(&["Alpha"]), &root).write_to(&out).unwrap();
+    assert_eq!(report.stale, paths(&["projects/beta/index.html", "stray.txt"]));
+    // 空になったディレクトリも消え、keep に当たるものは残る
+    assert!(!out.join("projects/beta").exists());
+    assert!(!out.join("stray.txt").exists());
+    assert!(out.join("projects/alpha/index.html").is_file());
+    assert!(out.join("CNAME").is_file());
+}
+
+#[test]
+fn write_to_removes_orphans_without_a_manifest() {
+    // .build-cache が無い（消した・初めて使う・--out を変えた）出力先でも、前のビルドの残りは消す
+    let root = temp_dir("no-manifest");
+    let out = root.join("dist");
+    builder(&site(&["Alpha", "Beta"]), &root).write_to(&out).unwrap();
+    fs::remove_file(root.join(".build-cache")).unwrap();
+
+    let report = builder(&site(&["Alpha"]), &root).write_to(&out).unwrap();
+    assert_eq!(report.stale, paths(&["projects/beta/index.html"]));
+    assert!(!out.join("projects/beta").exists());
+}
+
+#[test]
+fn dry_run_writes_and_removes_nothing() {
+    let root = temp_dir("dry-run");
+    let out = root.join("dist");
+    builder(&site(&["Alpha", "Beta"]), &root).write_to(&out).unwrap();
+    let manifest = fs::read(root.join(".build-cache")).unwrap();
+    fs::remove_file(out.join("index.html")).unwrap();
+
+    let report = builder(&site(&["Alpha"]), &root).dry_run(true).write_to(&out).unwrap();
+    assert!(report.dry_run);
+    assert_eq!(report.stale, paths(&["projects/beta/index.html"]));
+    assert!(report.written.contains(&PathBuf::from("index.html")));
+    assert!(out.join("projects/beta/index.html").is_file());
+    assert!(!out.join("index.html").exists());
+    assert_eq!(fs::read(root.join(".build-cache")).unwrap(), manifest);
+
+    // 出力先が無ければ作らない。root を出力先にしても dry_run なら止めずに何も書かない
+    let fresh = root.join("fresh");
+    builder(&site(&["Alpha"]), &root).dry_run(true).write_to(&fresh).unwrap();
+    assert!(!fresh.exists());
+    let before: BTreeSet<_> = fs::read_dir(&root).unwrap().map(|e| e.unwrap().file_name()).collect();
+    builder(&site(&["Alpha"]), &root).dry_run(true).write_to(&root).unwrap();
+    let after: BTreeSet<_> = fs::read_dir(&root).unwrap().map(|e| e.unwrap().file_name()).collect();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn write_to_refuses_an_out_that_contains_the_root() {
+    let root = temp_dir("root-out");
+    let e = builder(&site(&["Alpha"]), &root).write_to(&root).unwrap_err();
+    assert_eq!(e.exit_code(), 3);
+    assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+}
+
+// ---- SiteBuilder::render ----
+
+#[test]
+fn tags_with_the_same_slug_are_an_error() {
+    let root = temp_dir("tag-slugs");
+    let mut site = site(&["Alpha", "Beta"]);
+    site.projects[0].tags = vec!["c".into()];
+    site.projects[1].tags = vec!["c++".into()];
+    let e = builder(&site, &root).render().unwrap_err();
+    assert_eq!(e.exit_code(), 3);
+    assert!(e.to_string().contains("tags/c/"), "{e}");
+}
+
+#[test]
+fn pages_are_reused_unless_a_field_they_render_changes() {
+    let root = temp_dir("inputs");
+    let out = root.join("dist");
+    let page = PathBuf::from("projects/alpha/index.html");
+    let tag = PathBuf::from("tags/rust/index.html");
+    let mut site = site(&["Alpha"]);
+    site.projects[0].tags = vec!["rust".into()];
+    builder(&site, &root).write_to(&out).unwrap();
+
+    // 詳細ページにもカードにも出ない項目は入力に含めない
+    site.projects[0].weight = 5;
+    site.projects[0].tabs = vec!["3d".into()];
+    let report = builder(&site, &root).write_to(&out).unwrap();
+    assert!(report.reused.contains(&page) && report.reused.contains(&tag), "{report}");
+
+    // 詳細ページだけに出る項目はタグページを描き直さない
+    site.projects[0].role = "solo".into();
+    let report = builder(&site, &root).write_to(&out).unwrap();
+    assert!(report.rebuilt.contains(&page) && report.reused.contains(&tag), "{report}");
+
+    site.projects[0].description = "changed".into();
+    let report = builder(&site, &root).write_to(&out).unwrap();
+    assert!(report.rebuilt.contains(&page) && report.rebuilt.contains(&tag), "{report}");
+}
+
+// ---- --deny warnings ----
+
+#[test]
+fn deny_warnings_fails_with_exit_code_8_before_writing() {
+    let root = temp_dir("deny");
+    let out = root.join("dist");
+    let mut site = site(&["Alpha"]);
+    site.projects[0].github_url = "https://github.com/you/alpha".into();
+    site.projects[0].image_url = "https://picsum.photos/seed/alpha/800/600".into();
+
+    let e = builder(&site, &root).deny_warnings(true).write_to(&out).unwrap_err();
+    assert_eq!(e.exit_code(), 8, "{e}");
+    assert!(!out.exists());
+
+    // 警告だけなら既定では書き出す
+    builder(&site, &root).write_to(&out).unwrap();
+    assert!(out.join("index.html").is_file());
+}
+
+// ---- vCard ----
+
+#[test]
+fn contact_card_is_written_only_with_contact_qr() {
+    let root = temp_dir("vcard");
+    let mut site = site(&["Alpha"]);
+    site.contacts = vec![Contact { kind: "email".into(), url: "mailto:me@example.com".into(), label: "Mail".into(), obfuscate: Obfuscate::Rot13 }];
+
+    // 難読化した連絡先が contact.vcf から平文で漏れない
+    let files = builder(&site, &root).render().unwrap();
+    assert!(!files.contains_key(Path::new("contact.vcf")) && !files.contains_key(Path::new("assets/qr-contact.svg")));
+    assert!(files.contains_key(Path::new("assets/qr-site.svg")));
+    assert!(files.values().all(|b| !String::from_utf8_lossy(b).contains("me@example.com")));
+
+    site.contact_qr = true;
+    let files = builder(&site, &root).render().unwrap();
+    assert!(String::from_utf8_lossy(&files[Path::new("contact.vcf")]).contains("EMAIL:me@example.com"));
+    assert!(files.contains_key(Path::new("assets/qr-contact.svg")));
+}
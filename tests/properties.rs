@@ -0,0 +1,411 @@
+/*
+ * FileName:        properties
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 19:34:50
+ * LastModified:    2026-10-19 19:34:50
+ * Reference:       8ucchiman.github.io
+ * Description:     エスケープと JSON 出力のランダム入力テスト（シード付き PRNG + 縮小）
+ *
+ * PROPTEST_SEED=<u64> で乱数の種、PROPTEST_CASES=<n> で試行回数を変えられる。
+ * 失敗したときは縮小済みの入力と、再現用のシードを表示する。
+ */
+
+
+use std::{env, fmt::Debug};
+
+use bucchiman_github_io::{html_attr, html_escape, projects_to_json, taxonomy::Taxonomy, Project};
+
+// ---- properties ----
+
+#[test]
+fn html_escape_round_trips() {
+    check("html_escape", gen_string, |s| shrink_string(s), |s: &String| {
+        let out = html_escape(s);
+        if out.contains(['<', '>']) {
+            return Err(format!("raw `<` or `>` in {out:?}"));
+        }
+        expect_eq(&html_decode(&out)?, s)
+    });
+}
+
+#[test]
+fn html_attr_round_trips() {
+    check("html_attr", gen_string, |s| shrink_string(s), |s: &String| {
+        let out = html_attr(s);
+        if out.contains(['<', '>', '"']) {
+            return Err(format!("raw `<`, `>` or `\"` in {out:?}"));
+        }
+        expect_eq(&html_decode(&out)?, s)
+    });
+}
+
+/// JSON として読むと元の文字列に戻る。<script> に埋め込むので `<` `>` `&` は生のまま出さない。
+#[test]
+fn projects_json_parses_back() {
+    check("projects_to_json", gen_projects, |ps| shrink_projects(ps), |ps: &Vec<Fields>| {
+        let projects: Vec<Project> = ps.iter().map(Fields::to_project).collect();
+        let json = projects_to_json(&projects, &[], &Taxonomy::default());
+        if json.contains(['<', '>', '&', '\u{2028}', '\u{2029}']) {
+            return Err(format!("raw `<`, `>`, `&` or line separator in {json:?}"));
+        }
+        let Json::Array(items) = parse_json(&json)? else { return Err("top level is not an array".into()) };
+        if items.len() != ps.len() {
+            return Err(format!("{} items for {} projects", items.len(), ps.len()));
+        }
+        for (item, p) in items.iter().zip(ps) {
+            for (key, want) in FIELD_NAMES.iter().zip(&p.strings) {
+                let Some(Json::String(got)) = item.get(key) else { return Err(format!("`{key}` is not a string")) };
+                expect_eq(got, want)?;
+            }
+            let Some(Json::Array(tags)) = item.get("tags") else { return Err("`tags` is not an array".into()) };
+            let tags: Vec<String> = tags
+                .iter()
+                .map(|t| match t {
+                    Json::String(s) => Ok(s.clone()),
+                    _ => Err("tag is not a string".to_string()),
+                })
+                .collect::<Result<_, _>>()?;
+            expect_eq(&tags, &p.tags)?;
+        }
+        Ok(())
+    });
+}
+
+fn expect_eq<T: PartialEq + Debug>(got: &T, want: &T) -> Result<(), String> {
+    if got == want { Ok(()) } else { Err(format!("got {got:?}, want {want:?}")) }
+}
+
+// ---- harness ----
+
+/// xorshift64*。シードが同じなら同じ列になる
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// `prop` を `PROPTEST_CASES` 回試す。失敗したら `shrink` の候補で失敗し続ける限り小さくしてから panic する。
+fn check<T: Clone + Debug>(
+    name: &str,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    prop: impl Fn(&T) -> Result<(), String>,
+) {
+    let seed = env::var("PROPTEST_SEED").ok().and_then(|s| s.parse().ok()).unwrap_or(0x5eed_2026_u64);
+    let cases = env::var("PROPTEST_CASES").ok().and_then(|s| s.parse().ok()).unwrap_or(500);
+    let mut rng = Rng(seed | 1);
+    for case in 0..cases {
+        let input = generate(&mut rng);
+        let Err(first) = prop(&input) else { continue };
+        let (mut input, mut err) = (input, first);
+        'shrinking: loop {
+            for candidate in shrink(&input) {
+                if let Err(e) = prop(&candidate) {
+                    (input, err) = (candidate, e);
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+        panic!("property `{name}` failed on case {case} (PROPTEST_SEED={seed})\n  input: {input:?}\n  error: {err}");
+    }
+}
+
+// ---- generators ----
+
+/// エスケープで問題になりやすい文字に寄せたアルファベット
+const PIECES: &[&str] = &[
+    "a", "Z", "0", " ", "&", "<", ">", "\"", "'", "/", "\\", "\n", "\t", "\r", "\u{0}", "\u{1f}", "\u{7f}", "\u{2028}",
+    "&amp;", "&lt;", "&#39;", "&#x3c;", "&quot", "</script", "</SCRIPT>", "<!--", "]]>", "${", "`", "é", "日本", "🦀",
+];
+
+fn gen_string(rng: &mut Rng) -> String {
+    let len = rng.below(12);
+    (0..len).map(|_| PIECES[rng.below(PIECES.len())]).collect()
+}
+
+/// 短くする（半分・1 文字削る）か、文字を `a` に置き換える
+fn shrink_string(s: &str) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = Vec::new();
+    if chars.len() > 1 {
+        out.push(chars[..chars.len() / 2].iter().collect());
+        out.push(chars[chars.len() / 2..].iter().collect());
+    }
+    for i in 0..chars.len() {
+        let mut c = chars.clone();
+        c.remove(i);
+        out.push(c.into_iter().collect());
+    }
+    for i in 0..chars.len() {
+        if chars[i] != 'a' {
+            let mut c = chars.clone();
+            c[i] = 'a';
+            out.push(c.into_iter().collect());
+        }
+    }
+    out
+}
+
+const FIELD_NAMES: [&str; 7] = ["title", "description", "image_url", "github_url", "demo_url", "start", "end"];
+
+/// Project の JSON に出る文字列項目（FIELD_NAMES の順）とタグ
+#[derive(Clone, Debug)]
+struct Fields {
+    strings: [String; 7],
+    tags: Vec<String>,
+}
+
+impl Fields {
+    fn to_project(&self) -> Project {
+        let [title, description, image_url, github_url, demo_url, start, end] = self.strings.clone();
+        Project { title, description, image_url, github_url, demo_url, start, end, tags: self.tags.clone(), ..Default::default() }
+    }
+}
+
+fn gen_projects(rng: &mut Rng) -> Vec<Fields> {
+    let n = rng.below(4);
+    (0..n)
+        .map(|_| Fields {
+            strings: std::array::from_fn(|_| gen_string(rng)),
+            tags: (0..rng.below(3)).map(|_| gen_string(rng)).collect(),
+        })
+        .collect()
+}
+
+fn shrink_projects(ps: &[Fields]) -> Vec<Vec<Fields>> {
+    let mut out = Vec::new();
+    for i in 0..ps.len() {
+        let mut v = ps.to_vec();
+        v.remove(i);
+        out.push(v);
+    }
+    for (i, p) in ps.iter().enumerate() {
+        for f in 0..p.strings.len() {
+            for s in shrink_string(&p.strings[f]) {
+                let mut v = ps.to_vec();
+                v[i].strings[f] = s;
+                out.push(v);
+            }
+        }
+        for t in 0..p.tags.len() {
+            let mut v = ps.to_vec();
+            v[i].tags.remove(t);
+            out.push(v);
+            for s in shrink_string(&p.tags[t]) {
+                let mut v = ps.to_vec();
+                v[i].tags[t] = s;
+                out.push(v);
+            }
+        }
+    }
+    out
+}
+
+// ---- reference decoders ----
+
+/// 文字参照を戻す（名前付きは出力に使うものだけ）。`&` の後ろが参照でなければエラー。
+fn html_decode(s: &str) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(at) = rest.find('&') {
+        out.push_str(&rest[..at]);
+        let after = &rest[at + 1..];
+        let Some(end) = after.find(';') else { return Err(format!("bare `&` in {s:?}")) };
+        let name = &after[..end];
+        let c = match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "#39" | "apos" => '\'',
+            _ => {
+                let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => name.strip_prefix('#').and_then(|d| d.parse().ok()),
+                };
+                code.and_then(char::from_u32).ok_or_else(|| format!("bad reference `&{name};` in {s:?}"))?
+            }
+        };
+        out.push(c);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[derive(Debug)]
+enum Json {
+    Null,
+    Bool,
+    Number,
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+/// RFC 8259 の JSON を読む（生の制御文字は文字列中でもエラー）
+fn parse_json(src: &str) -> Result<Json, String> {
+    struct P<'a> {
+        s: &'a [u8],
+        i: usize,
+    }
+    impl P<'_> {
+        fn err<T>(&self, msg: &str) -> Result<T, String> {
+            Err(format!("json: {msg} at byte {}", self.i))
+        }
+        fn ws(&mut self) {
+            while self.i < self.s.len() && b" \t\r\n".contains(&self.s[self.i]) {
+                self.i += 1;
+            }
+        }
+        fn eat(&mut self, lit: &str) -> bool {
+            let ok = self.s[self.i..].starts_with(lit.as_bytes());
+            if ok {
+                self.i += lit.len();
+            }
+            ok
+        }
+        fn value(&mut self) -> Result<Json, String> {
+            self.ws();
+            let v = match self.s.get(self.i) {
+                Some(b'{') => {
+                    self.i += 1;
+                    let mut fields = Vec::new();
+                    self.ws();
+                    if !self.eat("}") {
+                        loop {
+                            self.ws();
+                            let Json::String(k) = self.value()? else { return self.err("object key must be a string") };
+                            self.ws();
+                            if !self.eat(":") {
+                                return self.err("expected `:`");
+                            }
+                            fields.push((k, self.value()?));
+                            self.ws();
+                            if self.eat("}") {
+                                break;
+                            }
+                            if !self.eat(",") {
+                                return self.err("expected `,` or `}`");
+                            }
+                        }
+                    }
+                    Json::Object(fields)
+                }
+                Some(b'[') => {
+                    self.i += 1;
+                    let mut items = Vec::new();
+                    self.ws();
+                    if !self.eat("]") {
+                        loop {
+                            items.push(self.value()?);
+                            self.ws();
+                            if self.eat("]") {
+                                break;
+                            }
+                            if !self.eat(",") {
+                                return self.err("expected `,` or `]`");
+                            }
+                        }
+                    }
+                    Json::Array(items)
+                }
+                Some(b'"') => Json::String(self.string()?),
+                Some(b't') if self.eat("true") => Json::Bool,
+                Some(b'f') if self.eat("false") => Json::Bool,
+                Some(b'n') if self.eat("null") => Json::Null,
+                Some(b'-' | b'0'..=b'9') => {
+                    self.i += 1;
+                    while self.i < self.s.len() && (self.s[self.i].is_ascii_digit() || b".eE+-".contains(&self.s[self.i])) {
+                        self.i += 1;
+                    }
+                    Json::Number
+                }
+                _ => return self.err("unexpected input"),
+            };
+            Ok(v)
+        }
+        fn hex4(&mut self) -> Result<u32, String> {
+            let h = self.s.get(self.i..self.i + 4).and_then(|h| std::str::from_utf8(h).ok());
+            match h.and_then(|h| u32::from_str_radix(h, 16).ok()) {
+                Some(v) => {
+                    self.i += 4;
+                    Ok(v)
+                }
+                None => self.err("bad \\u escape"),
+            }
+        }
+        fn string(&mut self) -> Result<String, String> {
+            self.i += 1;
+            let mut buf = Vec::new();
+            loop {
+                let Some(&b) = self.s.get(self.i) else { return self.err("unterminated string") };
+                self.i += 1;
+                match b {
+                    b'"' => break,
+                    0..=0x1f => return self.err("raw control character in string"),
+                    b'\\' => {
+                        let Some(&e) = self.s.get(self.i) else { return self.err("unterminated escape") };
+                        self.i += 1;
+                        let c = match e {
+                            b'"' => '"',
+                            b'\\' => '\\',
+                            b'/' => '/',
+                            b'b' => '\u{8}',
+                            b'f' => '\u{c}',
+                            b'n' => '\n',
+                            b'r' => '\r',
+                            b't' => '\t',
+                            b'u' => {
+                                let hi = self.hex4()?;
+                                let code = if (0xd800..0xdc00).contains(&hi) {
+                                    if !self.eat("\\u") {
+                                        return self.err("lone high surrogate");
+                                    }
+                                    let lo = self.hex4()?;
+                                    0x10000 + ((hi - 0xd800) << 10) + (lo.wrapping_sub(0xdc00) & 0x3ff)
+                                } else {
+                                    hi
+                                };
+                                match char::from_u32(code) {
+                                    Some(c) => c,
+                                    None => return self.err("invalid code point"),
+                                }
+                            }
+                            _ => return self.err("unknown escape"),
+                        };
+                        let mut tmp = [0; 4];
+                        buf.extend_from_slice(c.encode_utf8(&mut tmp).as_bytes());
+                    }
+                    _ => buf.push(b),
+                }
+            }
+            String::from_utf8(buf).or_else(|_| self.err("invalid UTF-8"))
+        }
+    }
+    let mut p = P { s: src.as_bytes(), i: 0 };
+    let v = p.value()?;
+    p.ws();
+    if p.i != p.s.len() {
+        return p.err("trailing input");
+    }
+    Ok(v)
+}
@@ -0,0 +1,283 @@
+/*
+ * FileName:        snapshots
+ * Author:          8ucchiman
+ * CreatedDate:     2026-10-19 18:52:13
+ * LastModified:    2026-10-19 18:52:13
+ * Reference:       8ucchiman.github.io
+ * Description:     生成物のスナップショットテスト（tests/snapshots/<name>.snap と比較）
+ *
+ * スナップショットの更新: UPDATE_SNAPSHOTS=1 cargo test --test snapshots
+ */
+
+
+use std::{env, fs, path::Path};
+
+use bucchiman_github_io::{
+    about::{self, About, Action},
+    contacts::{Contact, Obfuscate},
+    html_attr, html_escape,
+    icons::Icons,
+    index_page, pick_media_html, projects_to_json,
+    sections::{Entry, Section, SectionKind},
+    taxonomy::Taxonomy,
+    IndexInputs, Project, Site, Status, Tab,
+};
+
+// ---- fixtures ----
+
+fn strs(xs: &[&str]) -> Vec<String> {
+    xs.iter().map(|x| x.to_string()).collect()
+}
+
+fn tab(key: &str, gif_url: &str, video_url: &str) -> Tab {
+    Tab { key: key.into(), label: key.into(), description: format!("{key} tab"), gif_url: gif_url.into(), video_url: video_url.into() }
+}
+
+fn taxonomy() -> Taxonomy {
+    Taxonomy::from_toml(
+        r#"
+[3d]
+label = "3D"
+description = "Rendering & graphics."
+
+[opengl]
+label = "OpenGL"
+parent = "3d"
+aliases = ["gl"]
+
+[rust]
+label = "Rust"
+"#,
+    )
+    .unwrap()
+}
+
+fn projects() -> Vec<Project> {
+    vec![
+        Project {
+            title: "Path Tracer".into(),
+            image_url: "assets/pt.png".into(),
+            github_url: "https://github.com/me/pt".into(),
+            demo_url: "https://me.example/pt?a=1&b=2".into(),
+            tags: strs(&["rust", "3d"]),
+            tabs: strs(&["3d"]),
+            description: "BVH + GGX, \"physically\" based <fast>.".into(),
+            featured: true,
+            start: "2024-02".into(),
+            status: Some(Status::Active),
+            ..Default::default()
+        },
+        Project {
+            title: "GL Viewer".into(),
+            github_url: "https://github.com/me/gl".into(),
+            tags: strs(&["opengl"]),
+            description: "glTF viewer.".into(),
+            weight: 5,
+            start: "2023".into(),
+            end: "2023-11".into(),
+            status: Some(Status::Archived),
+            slug: "gl-viewer".into(),
+            ..Default::default()
+        },
+    ]
+}
+
+fn contacts() -> Vec<Contact> {
+    vec![
+        Contact { kind: "github".into(), url: "https://github.com/me".into(), label: "GitHub".into(), ..Default::default() },
+        Contact { kind: "email".into(), url: "mailto:me@example.com".into(), label: "Email".into(), obfuscate: Obfuscate::Reversed },
+    ]
+}
+
+fn about() -> About {
+    About {
+        kicker: "Rust / CV".into(),
+        title: "About me".into(),
+        name: "tester".into(),
+        roles: strs(&["Engineer"]),
+        bio: "Hello **world**.\n\nSecond & last paragraph.".into(),
+        portrait: String::new(),
+        portrait_alt: "portrait".into(),
+        actions: vec![Action { contact: "github".into(), label: String::new() }],
+    }
+}
+
+fn sections() -> Vec<Section> {
+    let section = |id: &str, kind| Section { id: id.into(), label: id.into(), kind, enabled: true };
+    let entry = |title: &str, date: &str| Entry { title: title.into(), url: format!("https://blog.example/{date}"), date: date.into(), note: String::new() };
+    vec![
+        section("home", SectionKind::Hero),
+        section("about", SectionKind::About),
+        section("projects", SectionKind::Projects),
+        section("timeline", SectionKind::Timeline),
+        section("posts", SectionKind::Posts(vec![entry("Older", "2023-01-02"), entry("Newer <b>", "2024-05")])),
+        section("notes", SectionKind::Markdown("Some *notes*.".into())),
+        section("contact", SectionKind::Contact),
+    ]
+}
+
+// ---- tests ----
+
+#[test]
+fn index_page_full() {
+    let tabs = vec![tab("3d", "assets/a.gif", "")];
+    let icons = Icons::load(Path::new("tests/no-such-icons")).unwrap();
+    let site = Site { sections: sections(), about: about(), contacts: contacts(), contact_qr: true, ..Default::default() };
+    let html = index_page(&site, &IndexInputs {
+        tabs: &tabs,
+        projects: &projects(),
+        related: &[vec![(1, 0.5)], vec![]],
+        taxonomy: &taxonomy(),
+        icons: &icons,
+        ver: "v1",
+        has_portrait: false,
+        year: 2025,
+        build_info: "",
+    });
+    snap("index_page_full", &html);
+}
+
+#[test]
+fn index_page_without_hero() {
+    let mut sections = sections();
+    sections.retain(|s| !matches!(s.kind, SectionKind::Hero | SectionKind::Posts(_) | SectionKind::Markdown(_)));
+    let icons = Icons::load(Path::new("tests/no-such-icons")).unwrap().with_inline(&["github"]);
+    let site = Site { sections, about: about(), contacts: contacts(), ..Default::default() };
+    let html = index_page(&site, &IndexInputs {
+        tabs: &[],
+        projects: &[],
+        related: &[],
+        taxonomy: &Taxonomy::default(),
+        icons: &icons,
+        ver: "dev",
+        has_portrait: true,
+        year: 2025,
+        build_info: "build abc123 · 2025-01-01",
+    });
+    snap("index_page_without_hero", &html);
+}
+
+#[test]
+fn about_actions_hide_obfuscated_contacts() {
+    let mut contacts = contacts();
+    contacts.push(Contact { kind: "work".into(), url: "mailto:work@example.com".into(), label: "Work".into(), obfuscate: Obfuscate::Entities });
+    let mut about = about();
+    about.actions.push(Action { contact: "email".into(), label: "Mail me".into() });
+    about.actions.push(Action { contact: "work".into(), label: String::new() });
+    let section = Section { id: "about".into(), label: "about".into(), kind: SectionKind::About, enabled: true };
+    let html = about::section_html(&section, &about, &contacts, false);
+    assert!(!html.contains("example.com"), "plain address in {html}");
+    snap("about_actions_obfuscated", &html);
+}
+
+#[test]
+fn pick_media_prefers_video() {
+    let tabs = vec![tab("a", "assets/a.gif", ""), tab("b", "", "assets/b.mp4?x=1&y=\"2\"")];
+    let (html, found) = pick_media_html(&tabs);
+    assert!(found);
+    snap("pick_media_video", &html);
+}
+
+#[test]
+fn pick_media_falls_back_to_gif() {
+    let tabs = vec![tab("a", "  ", " "), tab("b", "assets/b.gif", "")];
+    let (html, found) = pick_media_html(&tabs);
+    assert!(found);
+    snap("pick_media_gif", &html);
+}
+
+#[test]
+fn pick_media_placeholder() {
+    let (html, found) = pick_media_html(&[tab("a", "", "")]);
+    assert!(!found);
+    snap("pick_media_placeholder", &html);
+}
+
+#[test]
+fn projects_json() {
+    let json = projects_to_json(&projects(), &[vec![(1, 0.9)], vec![(0, 0.9)]], &taxonomy());
+    snap("projects_to_json", &json);
+}
+
+#[test]
+fn escapers() {
+    let inputs = ["plain", "a & b", "<script>alert(1)</script>", r#"say "hi" & 'bye'"#, "&amp; already", "日本語 <タグ>", ""];
+    let table: String = inputs
+        .iter()
+        .map(|s| format!("input:       {s}\nhtml_escape: {}\nhtml_attr:   {}\n\n", html_escape(s), html_attr(s)))
+        .collect();
+    snap("escapers", &table);
+}
+
+// ---- snapshot helper ----
+
+/// `tests/snapshots/<name>.snap` と比較する。違えば行単位の diff を出して失敗する。
+/// `UPDATE_SNAPSHOTS=1` なら比較せずに書き換える（無いものも作る）。
+fn snap(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(format!("{name}.snap"));
+    let actual = if actual.ends_with('\n') { actual.to_string() } else { format!("{actual}\n") };
+    if env::var_os("UPDATE_SNAPSHOTS").is_some_and(|v| v != "0") {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(_) => panic!("snapshot `{name}` does not exist ({}); run with UPDATE_SNAPSHOTS=1 to create it", path.display()),
+    };
+    if expected != actual {
+        panic!(
+            "snapshot `{name}` does not match ({})\n--- expected\n+++ actual\n{}\nrun with UPDATE_SNAPSHOTS=1 to accept the new output",
+            path.display(),
+            line_diff(&expected, &actual),
+        );
+    }
+}
+
+/// LCS による行 diff。変更の前後 2 行を文脈として出し、離れた塊の間は `@@` で区切る。
+fn line_diff(a: &str, b: &str) -> String {
+    const CONTEXT: usize = 2;
+    let a: Vec<&str> = a.lines().collect();
+    let b: Vec<&str> = b.lines().collect();
+    // lcs[i][j] = a[i..] と b[j..] の LCS 長
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+    let mut ops: Vec<(char, &str)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', a[i]));
+            i += 1;
+        } else {
+            ops.push(('+', b[j]));
+            j += 1;
+        }
+    }
+    let near_change = |k: usize| {
+        let lo = k.saturating_sub(CONTEXT);
+        let hi = (k + CONTEXT + 1).min(ops.len());
+        ops[lo..hi].iter().any(|(op, _)| *op != ' ')
+    };
+    let mut out = String::new();
+    let mut skipped = false;
+    for (k, (op, line)) in ops.iter().enumerate() {
+        if near_change(k) {
+            if skipped {
+                out.push_str("@@\n");
+                skipped = false;
+            }
+            out.push_str(&format!("{op}{line}\n"));
+        } else {
+            skipped = true;
+        }
+    }
+    out
+}